@@ -0,0 +1,43 @@
+//! Generates the table of embedded puzzle inputs for the `embed-inputs` feature.
+//!
+//! Inputs that are missing at build time are embedded as `None`, so a fresh
+//! clone still compiles and the affected days report the missing input at
+//! runtime instead.
+
+use std::{env, fs, path::PathBuf};
+
+const DAYS: u8 = 25;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=input");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let embed = env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some();
+
+    let mut missing = vec![];
+    let mut table = format!("static EMBEDDED: [Option<&str>; {DAYS}] = [\n");
+    for day in 1..=DAYS {
+        let path = manifest_dir.join("input").join(format!("day{day:02}.txt"));
+        if embed && path.is_file() {
+            table += &format!(
+                "    Some(include_str!({:?})),\n",
+                path.display().to_string()
+            );
+        } else {
+            table += "    None,\n";
+            missing.push(day.to_string());
+        }
+    }
+    table += "];\n";
+
+    if embed && !missing.is_empty() {
+        println!(
+            "cargo:warning=No input to embed for day(s) {}, they will fail at runtime.",
+            missing.join(", ")
+        );
+    }
+
+    fs::write(out_dir.join("embedded_inputs.rs"), table).unwrap();
+}
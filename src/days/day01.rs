@@ -1,51 +1,46 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::{error::parse_lines, Cancel, Solution, Solver};
 use anyhow::{anyhow, Context, Result};
 use once_cell::sync::Lazy;
 use pcre2::bytes::Regex;
 use std::str;
 
-const INPUT: &str = include_str!("../../input/day01.txt");
 static RE_DIGIT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?=(\d))").unwrap());
 static RE_ANY_NUMBER: Lazy<Regex> =
     Lazy::new(|| Regex::new("(?=(\\d|one|two|three|four|five|six|seven|eight|nine))").unwrap());
 ///////////////////////////////////////////////////////////////////////////////
 
-fn parse_input<'a>(data: &'a str, re: &'a Regex) -> Result<Vec<(u64, u64)>> {
-    data.lines()
-        .map(|line| {
-            // We need to search twice, since first and last digit could be the same.
-            let bline = line.as_bytes();
-
-            let first_digit_bytes: &[u8] = re
-                .captures_iter(bline)
-                .next()
-                .with_context(|| format!("No digit in line '{line}'"))??
-                .get(1)
-                .with_context(|| format!("Nothing captuerd in '{line}'"))?
-                .as_bytes();
-
-            let last_digit_bytes: &[u8] = re
-                .captures_iter(bline)
-                .last()
-                .with_context(|| format!("No digit in line '{line}'"))??
-                .get(1)
-                .with_context(|| format!("Nothing captuerd in '{line}'"))?
-                .as_bytes();
-
-            let first_digit = str::from_utf8(first_digit_bytes)?;
-            let last_digit = str::from_utf8(last_digit_bytes)?;
-
-            Ok((read_digit(first_digit)?, read_digit(last_digit)?))
-        })
-        .collect::<Result<Vec<(u64, u64)>>>()
+pub struct Day01;
+
+/// First and last digit of a line, once counting spelled out digits and once not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    digits: Option<(u64, u64)>,
+    numbers: (u64, u64),
+}
+
+fn parse_input(data: &str) -> Result<Vec<Calibration>> {
+    parse_lines(data, |line| {
+        let digits = first_and_last(line, &RE_DIGIT)?;
+        let numbers = first_and_last(line, &RE_ANY_NUMBER)?.context("No digit in line")?;
+        Ok(Calibration { digits, numbers })
+    })
 }
 
-fn calculate_result(data: &str, re: &Regex) -> Result<u64> {
-    let mut res = 0;
-    for (d1, d2) in parse_input(data, re)? {
-        res += 10 * d1 + d2
+fn first_and_last(line: &str, re: &Regex) -> Result<Option<(u64, u64)>> {
+    let mut first_and_last = None;
+    for cap in re.captures_iter(line.as_bytes()) {
+        let digit_bytes = cap?
+            .get(1)
+            .with_context(|| format!("Nothing captuerd in '{line}'"))?
+            .as_bytes();
+        let digit = read_digit(str::from_utf8(digit_bytes)?)?;
+        first_and_last = Some((first_and_last.map_or(digit, |(first, _)| first), digit));
     }
-    Ok(res)
+    Ok(first_and_last)
+}
+
+fn calculate_result<'a, I: IntoIterator<Item = &'a (u64, u64)>>(digits: I) -> u64 {
+    digits.into_iter().map(|(d1, d2)| 10 * d1 + d2).sum()
 }
 
 fn read_digit(d: &str) -> Result<u64> {
@@ -63,11 +58,24 @@ fn read_digit(d: &str) -> Result<u64> {
     }
 }
 
-pub fn solve() -> Result<SolutionPair> {
-    let sol1: u64 = calculate_result(INPUT, &RE_DIGIT)?;
-    let sol2: u64 = calculate_result(INPUT, &RE_ANY_NUMBER)?;
+impl Solver for Day01 {
+    type Parsed = Vec<Calibration>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1(calibrations: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        let digits = calibrations
+            .iter()
+            .map(|c| c.digits.as_ref().context("Line without a numeric digit"))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(calculate_result(digits).into())
+    }
 
-    Ok((Solution::U64(sol1), Solution::U64(sol2)))
+    fn part2(calibrations: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(calculate_result(calibrations.iter().map(|c| &c.numbers)).into())
+    }
 }
 
 #[cfg(test)]
@@ -78,14 +86,20 @@ mod tests {
 
     #[test]
     fn parse_input() -> super::Result<()> {
-        let _ = super::parse_input(TEST_INPUT, &super::RE_ANY_NUMBER)?;
+        let _ = super::parse_input(TEST_INPUT)?;
         Ok(())
     }
 
     #[test]
     fn calculate_result() -> super::Result<()> {
-        let result = super::calculate_result(TEST_INPUT, &super::RE_ANY_NUMBER)?;
+        let calibrations = super::parse_input(TEST_INPUT)?;
+        let result = super::calculate_result(calibrations.iter().map(|c| &c.numbers));
         assert_eq!(result, 364);
         Ok(())
     }
+
+    aoc_tests! {
+        super::Day01;
+        example: "day01.txt" => part2 = 364;
+    }
 }
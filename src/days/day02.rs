@@ -1,27 +1,28 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::{error::parse_lines, Cancel, Solution, Solver};
 use anyhow::{anyhow, Context, Result};
 use once_cell::sync::Lazy;
 use pcre2::bytes::Regex;
 use std::str;
 ///////////////////////////////////////////////////////////////////////////////
-const INPUT: &str = include_str!("../../input/day02.txt");
+static RE_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<=Game\s)\d+(?=:)").unwrap());
+static RE_ROUND: Lazy<Regex> = Lazy::new(|| Regex::new(r"([a-z\d\s,]+)(;|$)").unwrap());
+static RE_COLORS: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)(?:\s(red|green|blue))").unwrap());
+
+pub struct Day02;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Game {
+pub struct Game {
     id: u64,
     rounds: Vec<Round>,
 }
 
 impl Game {
-    const RE_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<=Game\s)\d+(?=:)").unwrap());
-    const RE_ROUND: Lazy<Regex> = Lazy::new(|| Regex::new(r"([a-z\d\s,]+)(;|$)").unwrap());
-
     fn from_bytes(s: &[u8]) -> Result<Self> {
-        let id_b = Self::RE_ID.find(s).unwrap().unwrap().as_bytes();
+        let id_b = RE_ID.find(s)?.context("Game id not found")?.as_bytes();
         let id: u64 = str::from_utf8(id_b)?.parse()?;
 
         let mut rounds = vec![];
-        for cap in Self::RE_ROUND.captures_iter(s) {
+        for cap in RE_ROUND.captures_iter(s) {
             let cap = cap?;
             let round_b = cap
                 .get(1)
@@ -61,13 +62,10 @@ impl Round {
         }
     }
 
-    const RE_COLORS: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(\d+)(?:\s(red|green|blue))").unwrap());
-
     fn from_bytes(s: &[u8]) -> Result<Self> {
         let mut round = Round::new(0, 0, 0);
 
-        for cap in Self::RE_COLORS.captures_iter(s).take(3) {
+        for cap in RE_COLORS.captures_iter(s).take(3) {
             let cap = cap?;
             let count_b = cap
                 .get(1)
@@ -124,16 +122,20 @@ fn part_2(games: &[Game]) -> u64 {
     games.iter().map(|game| game.min_set().power()).sum()
 }
 
-pub fn solve() -> Result<SolutionPair> {
-    let games: Vec<Game> = INPUT
-        .lines()
-        .map(|line| Game::from_bytes(line.as_bytes()))
-        .collect::<Result<Vec<Game>>>()?;
+impl Solver for Day02 {
+    type Parsed = Vec<Game>;
 
-    let sol1: u64 = part_1(&games);
-    let sol2: u64 = part_2(&games);
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_lines(input, |line| Game::from_bytes(line.as_bytes()))
+    }
 
-    Ok((Solution::U64(sol1), Solution::U64(sol2)))
+    fn part1(games: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(part_1(games).into())
+    }
+
+    fn part2(games: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(part_2(games).into())
+    }
 }
 
 #[cfg(test)]
@@ -194,4 +196,9 @@ mod tests {
 
         Ok(())
     }
+
+    aoc_tests! {
+        super::Day02;
+        example: "day02.txt" => part1 = 8, part2 = 2286;
+    }
 }
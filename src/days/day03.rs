@@ -1,16 +1,15 @@
-use crate::{Solution, SolutionPair};
-use anyhow::{anyhow, Context, Result};
+use crate::etc::{error::Error, Cancel, Solution, Solver};
+use anyhow::{anyhow, Result};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::str;
 
 ///////////////////////////////////////////////////////////////////////////////
-const INPUT: &str = include_str!("../../input/day03.txt");
 
-//
+pub struct Day03;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Schema {
+pub struct Schema {
     numbers: Vec<Number>,
     symbols: Vec<Symbol>,
 }
@@ -37,18 +36,37 @@ struct Symbol {
 
 impl Schema {
     fn from_str(s: &str) -> Result<Self> {
-        const RE_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
-        const RE_SYMBOL: Lazy<Regex> =
+        static RE_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+        static RE_SYMBOL: Lazy<Regex> =
             Lazy::new(|| Regex::new(r"(\+|/|\-|\$|=|&|#|%|@|\*)").unwrap());
 
         let nrows = s.lines().count();
         let ncols = s
             .lines()
             .next()
-            .with_context(|| "Empty String.")?
+            .ok_or_else(|| Error::parse(1, 1, "Empty input."))?
             .chars()
             .count();
 
+        for (row, line) in s.lines().enumerate() {
+            let len = line.chars().count();
+            if len != ncols {
+                return Err(Error::parse(
+                    row + 1,
+                    len.min(ncols) + 1,
+                    format!("Expected {ncols} columns, found {len}."),
+                )
+                .into());
+            }
+            if let Some((col, c)) = line
+                .chars()
+                .enumerate()
+                .find(|&(_, c)| c != '.' && !c.is_ascii_digit() && !Symbol::SYMBOLS.contains(&c))
+            {
+                return Err(Error::parse(row + 1, col + 1, format!("Unexpected '{c}'.")).into());
+            }
+        }
+
         let numbers: Vec<_> = RE_NUMBER
             .find_iter(s)
             .map(|re_match| {
@@ -66,7 +84,9 @@ impl Schema {
             .map(|re_match| {
                 let coordinate = Coordinate::from_linear(re_match.start(), nrows, ncols + 1);
                 let value = re_match.as_str();
-                Symbol::from_str(value, coordinate)
+                Symbol::from_str(value, coordinate).map_err(|err| {
+                    Error::parse(coordinate.row + 1, coordinate.col + 1, format!("{err:#}")).into()
+                })
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -157,16 +177,25 @@ fn part_2(schema: &Schema) -> u64 {
         .sum::<usize>() as u64
 }
 
-pub fn solve() -> Result<SolutionPair> {
-    let schema = Schema::from_str(INPUT)?;
-    let sol1: u64 = part_1(&schema);
-    let sol2: u64 = part_2(&schema);
+impl Solver for Day03 {
+    type Parsed = Schema;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Schema::from_str(input)
+    }
+
+    fn part1(schema: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(part_1(schema).into())
+    }
 
-    Ok((Solution::U64(sol1), Solution::U64(sol2)))
+    fn part2(schema: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(part_2(schema).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::etc::error::Error;
 
     const TEST_DATA_01: &str = include_str!("../../input/test/day03_01.txt");
 
@@ -181,30 +210,28 @@ mod tests {
         dbg!(&s1);
         dbg!(&s2);
 
-        assert_eq!(n1.is_adjacent(&s1.coordinate), false);
-        assert_eq!(n1.is_adjacent(&s2.coordinate), false);
+        assert!(!n1.is_adjacent(&s1.coordinate));
+        assert!(!n1.is_adjacent(&s2.coordinate));
     }
 
     #[test]
-    fn part_1() {
-        let schema = super::Schema::from_str(TEST_DATA_01).unwrap();
-        let part_numbers: usize = schema
-            .numbers
-            .iter()
-            .filter(|num| {
-                schema
-                    .symbols
-                    .iter()
-                    .any(|symbol| num.is_adjacent(&symbol.coordinate))
-            })
-            .map(|num| num.value)
-            .sum();
-        assert_eq!(part_numbers, 4361);
+    fn parse_errors() {
+        let err = super::Schema::from_str("").unwrap_err();
+        assert_eq!(Error::from_anyhow(&err), Error::parse(1, 1, "Empty input."));
+
+        let err = super::Schema::from_str("467..\n..*\n").unwrap_err();
+        assert_eq!(
+            Error::from_anyhow(&err),
+            Error::parse(2, 4, "Expected 5 columns, found 3.")
+        );
+
+        let err = super::Schema::from_str("467..\n..?..\n").unwrap_err();
+        assert_eq!(Error::from_anyhow(&err), Error::parse(2, 3, "Unexpected '?'."));
     }
 
-    #[test]
-    fn part_2() {
-        let schema = super::Schema::from_str(TEST_DATA_01).unwrap();
-        assert_eq!(super::part_2(&schema), 467835)
+    aoc_tests! {
+        super::Day03;
+        example_01: "day03_01.txt" => part1 = 4361, part2 = 467835;
+        example_02: "day03_02.txt" => part1 = 925, part2 = 6756;
     }
 }
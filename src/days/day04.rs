@@ -1,14 +1,15 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::{error::parse_lines, Cancel, Solution, Solver};
 use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::BTreeSet;
 
 ///////////////////////////////////////////////////////////////////////////////
-const INPUT: &str = include_str!("../../input/day04.txt");
+
+pub struct Day04;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Card {
+pub struct Card {
     id: usize,
     winners: BTreeSet<u64>,
     numbers: BTreeSet<u64>,
@@ -16,9 +17,9 @@ struct Card {
 
 impl Card {
     fn from_str(s: &str) -> Result<Self> {
-        const RE_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+        static RE_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
 
-        let mut parts = s.split(|c| c == ':' || c == '|');
+        let mut parts = s.split([':', '|']);
         let head_s = parts
             .next()
             .with_context(|| format!("Invalid input '{s}'. "))?;
@@ -69,14 +70,11 @@ impl Card {
     }
 }
 
-pub fn solve() -> Result<SolutionPair> {
-    let cards = INPUT
-        .lines()
-        .map(|line| Card::from_str(line))
-        .collect::<Result<Vec<Card>>>()?;
-
-    let sol1: u64 = cards.iter().map(|card| card.score()).sum();
+fn part_1(cards: &[Card]) -> u64 {
+    cards.iter().map(|card| card.score()).sum()
+}
 
+fn part_2(cards: &[Card]) -> u64 {
     let mut counts: Vec<_> = cards
         .iter()
         .map(|card| (card.id, 1, card.count_matching_numbers()))
@@ -86,14 +84,28 @@ pub fn solve() -> Result<SolutionPair> {
         let (card_id, count, score) = counts[i];
         assert_eq!(card_id, i + 1);
 
-        for j in 1..=score as usize {
+        for j in 1..=score {
             counts[i + j].1 += count
         }
     }
 
-    let sol2: u64 = counts.iter().map(|(_, count, _)| count).sum();
+    counts.iter().map(|(_, count, _)| count).sum()
+}
+
+impl Solver for Day04 {
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_lines(input, Card::from_str)
+    }
+
+    fn part1(cards: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(part_1(cards).into())
+    }
 
-    Ok((Solution::U64(sol1), Solution::U64(sol2)))
+    fn part2(cards: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Ok(part_2(cards).into())
+    }
 }
 
 #[cfg(test)]
@@ -105,7 +117,7 @@ mod tests {
     fn card_from_str() -> Result<()> {
         let cards = TEST_INPUT
             .lines()
-            .map(|line| super::Card::from_str(line))
+            .map(super::Card::from_str)
             .collect::<Result<Vec<super::Card>>>()?;
 
         let score: u64 = cards.iter().map(|card| card.score()).sum();
@@ -113,31 +125,8 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn part_2() -> Result<()> {
-        let cards = TEST_INPUT
-            .lines()
-            .map(|line| super::Card::from_str(line))
-            .collect::<Result<Vec<super::Card>>>()?;
-
-        let mut counts: Vec<_> = cards
-            .iter()
-            .map(|card| (card.id, 1, card.count_matching_numbers()))
-            .collect();
-
-        for i in 0..counts.len() {
-            let (card_id, count, score) = counts[i];
-            assert_eq!(card_id, i + 1);
-
-            for j in 1..=score as usize {
-                match counts.get_mut(i + j) {
-                    Some((_, new_count, _)) => *new_count += count,
-                    None => continue,
-                }
-            }
-        }
-        let total: u64 = counts.iter().map(|(_, count, _)| count).sum();
-        assert_eq!(total, 30);
-        Ok(())
+    aoc_tests! {
+        super::Day04;
+        example: "day04.txt" => part1 = 13, part2 = 30;
     }
 }
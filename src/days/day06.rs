@@ -1,11 +1,16 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::Solver;
 use anyhow::Result;
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve() -> Result<SolutionPair> {
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
+pub struct Day06;
 
-    Ok((Solution::U64(sol1), Solution::U64(sol2)))
+impl Solver for Day06 {
+    type Parsed = ();
+
+    const IMPLEMENTED: bool = false;
+
+    fn parse(_input: &str) -> Result<Self::Parsed> {
+        Ok(())
+    }
 }
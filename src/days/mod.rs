@@ -1,25 +1,100 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+use crate::etc::solver::{self, Part, Solver};
+
+/// One puzzle of the calendar together with its solver.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    /// Runs the day's `Solver` on an input, timing each phase.
+    pub run: solver::RunFn,
+    /// `Solver::IMPLEMENTED` of the day's solver.
+    pub implemented: bool,
+    /// `Solver::HAS_PART2` of the day's solver.
+    pub has_part2: bool,
+}
+
+impl Day {
+    /// Whether the puzzle has `part`, day 25 has no second part.
+    pub fn has(&self, part: Part) -> bool {
+        part == Part::One || self.has_part2
+    }
+}
+
+/// Declares the day modules and the `DAYS` registry in one place.
+macro_rules! calendar {
+    ($($number:literal => $module:ident::$solver:ident, $title:literal;)*) => {
+        $(pub mod $module;)*
+
+        pub static DAYS: &[Day] = &[$(
+            Day {
+                number: $number,
+                title: $title,
+                run: solver::run::<$module::$solver>,
+                implemented: <$module::$solver as Solver>::IMPLEMENTED,
+                has_part2: <$module::$solver as Solver>::HAS_PART2,
+            },
+        )*];
+    };
+}
+
+/// Generates one test per example that runs the real solver on
+/// `input/test/<file>` and checks the given parts.
+///
+/// ```ignore
+/// aoc_tests! {
+///     Day03;
+///     example_01: "day03_01.txt" => part1 = 4361, part2 = 467835;
+///     example_02: "day03_02.txt" => part1 = 925;
+/// }
+/// ```
+#[cfg(test)]
+macro_rules! aoc_tests {
+    ($solver:ty; $($name:ident: $file:literal => $($part:ident = $expected:expr),+;)+) => {
+        $(
+            #[test]
+            fn $name() -> anyhow::Result<()> {
+                use $crate::etc::{Cancel, Solution, Solver};
+                const INPUT: &str =
+                    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/test/", $file));
+
+                let parsed = <$solver as Solver>::parse(INPUT)?;
+                $(
+                    let solution = <$solver as Solver>::$part(&parsed, &Cancel::new())?;
+                    assert_eq!(solution, Solution::from($expected), stringify!($part));
+                )+
+                Ok(())
+            }
+        )+
+    };
+}
+
+calendar! {
+    1 => day01::Day01, "Trebuchet?!";
+    2 => day02::Day02, "Cube Conundrum";
+    3 => day03::Day03, "Gear Ratios";
+    4 => day04::Day04, "Scratchcards";
+    5 => day05::Day05, "If You Give A Seed A Fertilizer";
+    6 => day06::Day06, "Wait For It";
+    7 => day07::Day07, "Camel Cards";
+    8 => day08::Day08, "Haunted Wasteland";
+    9 => day09::Day09, "Mirage Maintenance";
+    10 => day10::Day10, "Pipe Maze";
+    11 => day11::Day11, "Cosmic Expansion";
+    12 => day12::Day12, "Hot Springs";
+    13 => day13::Day13, "Point of Incidence";
+    14 => day14::Day14, "Parabolic Reflector Dish";
+    15 => day15::Day15, "Lens Library";
+    16 => day16::Day16, "The Floor Will Be Lava";
+    17 => day17::Day17, "Clumsy Crucible";
+    18 => day18::Day18, "Lavaduct Lagoon";
+    19 => day19::Day19, "Aplenty";
+    20 => day20::Day20, "Pulse Propagation";
+    21 => day21::Day21, "Step Counter";
+    22 => day22::Day22, "Sand Slabs";
+    23 => day23::Day23, "A Long Walk";
+    24 => day24::Day24, "Never Tell Me The Odds";
+    25 => day25::Day25, "Snowverload";
+}
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
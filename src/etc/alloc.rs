@@ -0,0 +1,143 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Wraps the system allocator and counts the allocations of each thread.
+pub struct Counting;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+struct Counters {
+    count: Cell<u64>,
+    bytes: Cell<u64>,
+    /// Can become negative when memory allocated by another thread is freed.
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            count: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+fn record_alloc(size: usize) {
+    let _ = COUNTERS.try_with(|c| {
+        c.count.set(c.count.get() + 1);
+        c.bytes.set(c.bytes.get() + size as u64);
+        c.live.set(c.live.get() + size as i64);
+        c.peak.set(c.peak.get().max(c.live.get()));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let _ = COUNTERS.try_with(|c| c.live.set(c.live.get() - size as i64));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made by the current thread while running a closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Number of allocations, a `realloc` counts as one.
+    pub count: u64,
+    /// Total bytes allocated.
+    pub bytes: u64,
+    /// Highest number of bytes live at the same time, above what was live before.
+    pub peak_bytes: u64,
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+    let (count, bytes, live) = COUNTERS.with(|c| {
+        c.peak.set(c.live.get());
+        (c.count.get(), c.bytes.get(), c.live.get())
+    });
+
+    let value = f();
+
+    let stats = COUNTERS.with(|c| AllocStats {
+        count: c.count.get() - count,
+        bytes: c.bytes.get() - bytes,
+        peak_bytes: (c.peak.get() - live).max(0) as u64,
+    });
+    (value, stats)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `12.3 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{value:.1} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, measure};
+
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let a = vec![0u8; 1000];
+            let b = vec![0u8; 500];
+            drop(a);
+            drop(b);
+            let _c: Vec<u8> = Vec::with_capacity(200);
+        });
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.bytes, 1700);
+        assert_eq!(stats.peak_bytes, 1500);
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,283 @@
+use super::{solver::Part, Answers, Solution};
+use anyhow::{bail, Context, Result};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Directory holding the known-good answers as `dayNN.txt`.
+pub const DIR: &str = "answers";
+/// Indentation of the lines of a multi-line answer.
+const BLOCK_INDENT: &str = "  ";
+
+/// Known-good answers of a day, stored as
+///
+/// ```text
+/// part1: 142
+/// part2: 281
+/// ```
+///
+/// Either part may be missing, blank lines and lines starting with `#` are ignored.
+/// Multi-line answers follow a `|` on lines indented by two spaces:
+///
+/// ```text
+/// part2: |
+///   #..#
+///   ####
+/// ```
+///
+/// The lines of a block are kept as they are apart from the indentation. An
+/// empty line followed by more indented lines is a blank row of the block, as
+/// editors tend to strip the indentation of otherwise blank lines.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Known {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Result of comparing an answer against the known-good one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong { .. } => "wrong",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Known {
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut known = Known::default();
+        let mut lines = s.lines().enumerate().peekable();
+        while let Some((i, line)) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .with_context(|| format!("Line {}: expected 'part1: <answer>'.", i + 1))?;
+            let mut value = value.trim().to_string();
+            if value == "|" {
+                let mut block = vec![];
+                while let Some(&(_, line)) = lines.peek() {
+                    if let Some(row) = line.strip_prefix(BLOCK_INDENT) {
+                        block.push(row);
+                    } else if line.trim().is_empty() && continues_block(lines.clone()) {
+                        block.push("");
+                    } else {
+                        break;
+                    }
+                    lines.next();
+                }
+                if block.is_empty() {
+                    bail!("Line {}: expected indented lines after '|'.", i + 1);
+                }
+                value = block.join("\n");
+            }
+            let value = Some(value);
+            match key.trim() {
+                "part1" => known.part1 = value,
+                "part2" => known.part2 = value,
+                key => bail!("Line {}: unknown key '{key}'.", i + 1),
+            }
+        }
+        Ok(known)
+    }
+
+    /// Loads the known answers of `day`, which are all unknown if there is no file.
+    pub fn load(day: u8) -> Result<Self> {
+        Known::load_file(&path(day))
+    }
+
+    /// Loads known answers from `path`, which are all unknown if it does not exist.
+    pub fn load_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Known::default());
+        }
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("Could not read the answers from '{}'.", path.display()))?;
+        Known::parse(&s).with_context(|| format!("Invalid answers file '{}'.", path.display()))
+    }
+
+    /// Stores `answers` as the new known-good answers of `day`.
+    ///
+    /// Parts that were not run keep their previous answer.
+    pub fn record(day: u8, answers: &Answers) -> Result<()> {
+        Known::record_file(&path(day), answers)
+    }
+
+    /// Like `record`, but stores the answers in `path`.
+    pub fn record_file(path: &Path, answers: &Answers) -> Result<()> {
+        let mut known = Known::load_file(path)?;
+        if let Some(p1) = answers.part1.solution() {
+            known.part1 = Some(p1.to_string());
+        }
+        if let Some(p2) = answers.part2.solution() {
+            known.part2 = Some(p2.to_string());
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create '{}'.", dir.display()))?;
+        }
+        fs::write(path, known.to_string())
+            .with_context(|| format!("Could not write the answers to '{}'.", path.display()))
+    }
+
+    /// Compares `answers` with the known ones, `None` for parts without a solution.
+    pub fn verify(&self, answers: &Answers) -> [Option<Verdict>; 2] {
+        Part::ALL.map(|part| {
+            let expected = match part {
+                Part::One => &self.part1,
+                Part::Two => &self.part2,
+            };
+            answers[part]
+                .solution()
+                .map(|solution| verdict(solution, expected))
+        })
+    }
+}
+
+impl Display for Known {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (key, value) in [("part1", &self.part1), ("part2", &self.part2)] {
+            match value {
+                Some(value) if value.contains('\n') => {
+                    writeln!(f, "{key}: |")?;
+                    for line in value.lines() {
+                        writeln!(f, "{BLOCK_INDENT}{line}")?;
+                    }
+                }
+                Some(value) => writeln!(f, "{key}: {value}")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the first non-blank line of `lines` is indented, i.e. the blank
+/// lines before it are rows of the current block.
+fn continues_block<'a>(mut lines: impl Iterator<Item = (usize, &'a str)>) -> bool {
+    lines
+        .find(|(_, line)| !line.trim().is_empty())
+        .is_some_and(|(_, line)| line.starts_with(BLOCK_INDENT))
+}
+
+fn verdict(solution: &Solution, expected: &Option<String>) -> Verdict {
+    match expected {
+        None => Verdict::Unknown,
+        Some(expected) if expected.parse() == Ok(solution.clone()) => Verdict::Correct,
+        Some(expected) => Verdict::Wrong {
+            expected: expected.clone(),
+        },
+    }
+}
+
+fn path(day: u8) -> PathBuf {
+    Path::new(DIR).join(format!("day{day:02}.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Known, Verdict};
+    use crate::etc::{Answer, Answers, Solution};
+
+    #[test]
+    fn parse() {
+        let known = Known::parse("# day 1\npart1: 142\n\npart2:  281 \n").unwrap();
+        assert_eq!(known.part1.as_deref(), Some("142"));
+        assert_eq!(known.part2.as_deref(), Some("281"));
+
+        assert!(Known::parse("part3: 1").is_err());
+        assert!(Known::parse("142").is_err());
+    }
+
+    #[test]
+    fn display() {
+        let known = Known {
+            part1: None,
+            part2: Some("281".to_string()),
+        };
+        assert_eq!(known.to_string(), "part2: 281\n");
+        assert_eq!(Known::parse(&known.to_string()).unwrap(), known);
+    }
+
+    #[test]
+    fn multiline() {
+        let grid = Solution::from(vec!["#..#".to_string(), "# ##".to_string()]);
+        let mut known = Known::parse("part1: 1").unwrap();
+        known.part2 = Some(grid.to_string());
+        assert_eq!(known.to_string(), "part1: 1\npart2: |\n  #..#\n  # ##\n");
+
+        let parsed = Known::parse(&known.to_string()).unwrap();
+        assert_eq!(parsed, known);
+        let verdicts = parsed.verify(&Answers {
+            part1: Answer::NotRun,
+            part2: Answer::Solved(grid),
+        });
+        assert_eq!(verdicts, [None, Some(Verdict::Correct)]);
+
+        assert!(Known::parse("part1: |\npart2: 3").is_err());
+    }
+
+    #[test]
+    fn block_with_blank_row() {
+        let grid = "#  #\n\n ## ".to_string();
+        let known = Known {
+            part1: Some(grid.clone()),
+            part2: Some("7".to_string()),
+        };
+        assert_eq!(
+            known.to_string(),
+            "part1: |\n  #  #\n  \n   ## \npart2: 7\n"
+        );
+        assert_eq!(Known::parse(&known.to_string()).unwrap(), known);
+
+        // The same file after an editor stripped the trailing whitespace.
+        let parsed = Known::parse("part1: |\n  #  #\n\n   ## \n\npart2: 7\n").unwrap();
+        assert_eq!(parsed, known);
+        let parsed = Known::parse("part1: |\n  #  #\n\n# comment\npart2: 7\n").unwrap();
+        assert_eq!(parsed.part1.as_deref(), Some("#  #"));
+    }
+
+    #[test]
+    fn verify() {
+        let known = Known::parse("part1: 142").unwrap();
+        let verdicts = known.verify(&Answers {
+            part1: Answer::Solved(Solution::U64(142)),
+            part2: Answer::Solved(Solution::U64(281)),
+        });
+        assert_eq!(verdicts, [Some(Verdict::Correct), Some(Verdict::Unknown)]);
+
+        let verdicts = known.verify(&Answers {
+            part1: Answer::Solved(Solution::I32(142)),
+            part2: Answer::NotRun,
+        });
+        assert_eq!(verdicts, [Some(Verdict::Correct), None]);
+
+        let verdicts = known.verify(&Answers {
+            part1: Answer::Solved(Solution::I32(141)),
+            part2: Answer::Unsolved,
+        });
+        assert_eq!(
+            verdicts,
+            [
+                Some(Verdict::Wrong {
+                    expected: "142".to_string()
+                }),
+                None
+            ]
+        );
+    }
+}
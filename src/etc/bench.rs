@@ -0,0 +1,216 @@
+use super::solver::{millis, Timings};
+use anyhow::Result;
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// Runs made before measuring, to warm up caches and lazily built statics.
+const WARMUP_RUNS: u32 = 3;
+/// Time budget per day when neither a number of runs nor `--bench-time` is given.
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+const MIN_RUNS: u32 = 10;
+const MAX_RUNS: u32 = 100_000;
+
+/// How often a day is run in benchmark mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runs {
+    /// Exactly this many timed runs.
+    Count(u32),
+    /// As many runs as fit into the time budget, within `MIN_RUNS..=MAX_RUNS`.
+    Budget(Duration),
+}
+
+impl Display for Runs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Runs::Count(n) => write!(f, "{n} runs"),
+            Runs::Budget(budget) => write!(f, "{}s per day", budget.as_secs_f64()),
+        }
+    }
+}
+
+/// Summary statistics over the timed runs, all durations in milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Runs outside of the Tukey fences, i.e. more than 1.5 IQR away from the quartiles.
+    pub outliers: usize,
+}
+
+/// A phase of a solver, as benchmarked and stored in the timing history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+    Total,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Parse, Phase::Part1, Phase::Part2, Phase::Total];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+            Phase::Total => "total",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Phase::ALL.into_iter().find(|phase| phase.name() == name)
+    }
+
+    fn of(&self, timings: &Timings) -> Option<Duration> {
+        match self {
+            Phase::Parse => Some(timings.parse),
+            Phase::Part1 => timings.part1,
+            Phase::Part2 => timings.part2,
+            Phase::Total => Some(timings.total()),
+        }
+    }
+}
+
+/// Statistics of every phase that was run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchStats {
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+impl BenchStats {
+    pub fn get(&self, phase: Phase) -> Option<&Stats> {
+        self.phases
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|(_, stats)| stats)
+    }
+
+    pub fn total(&self) -> &Stats {
+        self.get(Phase::Total)
+            .expect("The total is always measured")
+    }
+}
+
+/// Times each phase of `f` according to `runs`, after a few warmup runs.
+pub fn bench<F: FnMut() -> Result<Timings>>(runs: Runs, mut f: F) -> Result<BenchStats> {
+    for _ in 0..WARMUP_RUNS {
+        std::hint::black_box(f()?);
+    }
+
+    let mut samples: Vec<Timings> = vec![];
+    let start = Instant::now();
+    loop {
+        let done = match runs {
+            Runs::Count(n) => samples.len() >= n as usize,
+            Runs::Budget(budget) => {
+                samples.len() >= MAX_RUNS as usize
+                    || (samples.len() >= MIN_RUNS as usize && start.elapsed() >= budget)
+            }
+        };
+        if done {
+            break;
+        }
+
+        samples.push(std::hint::black_box(f()?));
+    }
+
+    let phases = Phase::ALL
+        .into_iter()
+        .filter_map(|phase| {
+            let samples: Option<Vec<f64>> = samples
+                .iter()
+                .map(|timings| phase.of(timings).map(millis))
+                .collect();
+            Some((phase, Stats::from_samples(samples?)))
+        })
+        .collect();
+    Ok(BenchStats { phases })
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let runs = samples.len();
+        if runs == 0 {
+            return Stats {
+                runs,
+                min: 0.0,
+                median: 0.0,
+                mean: 0.0,
+                std_dev: 0.0,
+                outliers: 0,
+            };
+        }
+
+        let mean = samples.iter().sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (runs - 1) as f64
+        } else {
+            0.0
+        };
+
+        let (q1, q3) = (quantile(&samples, 0.25), quantile(&samples, 0.75));
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        let outliers = samples.iter().filter(|&&x| x < low || x > high).count();
+
+        Stats {
+            runs,
+            min: samples[0],
+            median: quantile(&samples, 0.5),
+            mean,
+            std_dev: variance.sqrt(),
+            outliers,
+        }
+    }
+}
+
+/// Linearly interpolated quantile of sorted, non-empty `samples`.
+fn quantile(samples: &[f64], q: f64) -> f64 {
+    let pos = q * (samples.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    samples[lo] + (samples[hi] - samples[lo]) * (pos - lo as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bench, Phase, Runs, Stats};
+    use crate::etc::solver::Timings;
+    use std::time::Duration;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(vec![3.0, 1.0, 2.0, 4.0, 100.0]);
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 22.0);
+        assert_eq!(stats.outliers, 1);
+        assert!((stats.std_dev - 43.6176).abs() < 1e-3);
+    }
+
+    #[test]
+    fn run_count() -> anyhow::Result<()> {
+        let mut calls = 0;
+        let stats = bench(Runs::Count(7), || {
+            calls += 1;
+            Ok(Timings {
+                parse: Duration::from_millis(2),
+                part1: Some(Duration::from_millis(1)),
+                part2: None,
+            })
+        })?;
+        assert_eq!(calls, 10);
+        assert_eq!(stats.total().runs, 7);
+        assert_eq!(stats.total().median, 3.0);
+        assert_eq!(stats.get(Phase::Part1).unwrap().median, 1.0);
+        assert_eq!(stats.get(Phase::Part2), None);
+        Ok(())
+    }
+}
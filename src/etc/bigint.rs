@@ -0,0 +1,517 @@
+use anyhow::{bail, Error, Result};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub},
+    str::FromStr,
+};
+
+/// Largest power of ten that fits into a limb, used for parsing and printing.
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+/// Unsigned integer of arbitrary size.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, without trailing zeros.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    /// The value as `u128`, `None` if it does not fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0, |n, &limb| (n << 32) | limb as u128),
+        )
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        let mut borrow = 0;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let sub = *other.limbs.get(i).unwrap_or(&0) as i64 + borrow;
+            let diff = *limb as i64 - sub;
+            borrow = (diff < 0) as i64;
+            *limb = diff.rem_euclid(1 << 32) as u32;
+        }
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    /// Quotient and remainder, panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+        if let [small] = divisor.limbs[..] {
+            let (quotient, remainder) = self.div_rem_small(small);
+            return (quotient, BigUint::from(remainder));
+        }
+
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut remainder = BigUint::zero();
+        for bit in (0..self.limbs.len() * 32).rev() {
+            remainder.shl1(self.limbs[bit / 32] >> (bit % 32) & 1);
+            if remainder >= *divisor {
+                remainder = remainder.checked_sub(divisor).unwrap();
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+        (BigUint::from_limbs(quotient), remainder)
+    }
+
+    fn div_rem_small(&self, divisor: u32) -> (BigUint, u32) {
+        let mut limbs = self.limbs.clone();
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        (BigUint::from_limbs(limbs), remainder as u32)
+    }
+
+    /// Shifts left by one bit and sets the lowest bit to `bit`.
+    fn shl1(&mut self, bit: u32) {
+        let mut carry = bit;
+        for limb in &mut self.limbs {
+            let next = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    /// `self * factor + summand`, used for parsing.
+    fn mul_add_small(&mut self, factor: u32, summand: u32) {
+        let mut carry = summand as u64;
+        for limb in &mut self.limbs {
+            let value = *limb as u64 * factor as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    /// Least common multiple, zero if either is zero.
+    pub fn lcm(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        &self.div_rem(&self.gcd(other)).0 * other
+    }
+
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let (mut base, mut result) = (self.clone(), BigUint::from(1u8));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+macro_rules! biguint_from {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for BigUint {
+            fn from(n: $ty) -> Self {
+                let n = n as u128;
+                BigUint::from_limbs((0..4).map(|i| (n >> (32 * i)) as u32).collect())
+            }
+        }
+    )*};
+}
+
+biguint_from!(u8, u16, u32, u64, u128, usize);
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64
+                + *other.limbs.get(i).unwrap_or(&0) as u64
+                + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let value = limbs[i + j] as u64 + a as u64 * b as u64 + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s.strip_prefix('+').unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            bail!("Invalid integer '{s}'.");
+        }
+        let mut n = BigUint::zero();
+        for chunk in digits.as_bytes().chunks(DECIMAL_DIGITS) {
+            let value = std::str::from_utf8(chunk)?.parse()?;
+            n.mul_add_small(10u32.pow(chunk.len() as u32), value);
+        }
+        Ok(BigUint::from_limbs(n.limbs))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_decimal())
+    }
+}
+
+impl BigUint {
+    fn to_decimal(&self) -> String {
+        let mut chunks = vec![];
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (quotient, chunk) = n.div_rem_small(DECIMAL_BASE);
+            chunks.push(chunk);
+            n = quotient;
+        }
+        let mut s = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            s += &format!("{chunk:0width$}", width = DECIMAL_DIGITS);
+        }
+        s
+    }
+}
+
+/// Signed integer of arbitrary size.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    /// Never set for zero.
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    pub fn new(negative: bool, magnitude: BigUint) -> Self {
+        BigInt {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    /// The value as `i128`, `None` if it does not fit.
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        match self.negative {
+            true => 0i128.checked_sub_unsigned(magnitude),
+            false => i128::try_from(magnitude).ok(),
+        }
+    }
+
+    /// The value as `u128`, `None` if it is negative or does not fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.negative {
+            true => None,
+            false => self.magnitude.to_u128(),
+        }
+    }
+
+    /// Quotient rounded towards zero and remainder with the sign of `self`,
+    /// like the primitive integers. Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        let (quotient, remainder) = self.magnitude.div_rem(&divisor.magnitude);
+        (
+            BigInt::new(self.negative != divisor.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        )
+    }
+
+    /// Greatest common divisor, never negative.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        BigInt::from(self.magnitude.gcd(&other.magnitude))
+    }
+}
+
+macro_rules! bigint_from {
+    (unsigned: $($unsigned:ty),*; signed: $($signed:ty),*) => {
+        $(impl From<$unsigned> for BigInt {
+            fn from(n: $unsigned) -> Self {
+                BigInt::new(false, BigUint::from(n))
+            }
+        })*
+        $(impl From<$signed> for BigInt {
+            fn from(n: $signed) -> Self {
+                BigInt::new(n < 0, BigUint::from((n as i128).unsigned_abs()))
+            }
+        })*
+    };
+}
+
+bigint_from!(unsigned: u8, u16, u32, u64, u128, usize; signed: i8, i16, i32, i64, i128, isize);
+
+impl From<BigUint> for BigInt {
+    fn from(magnitude: BigUint) -> Self {
+        BigInt::new(false, magnitude)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (negative, _) => match negative {
+                true => Ordering::Less,
+                false => Ordering::Greater,
+            },
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, &self.magnitude + &other.magnitude);
+        }
+        match self.magnitude.checked_sub(&other.magnitude) {
+            Some(magnitude) => BigInt::new(self.negative, magnitude),
+            None => BigInt::new(
+                other.negative,
+                other.magnitude.checked_sub(&self.magnitude).unwrap(),
+            ),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            &self.magnitude * &other.magnitude,
+        )
+    }
+}
+
+/// Implements the owned forms of an operator and its assigning version in
+/// terms of the one on references.
+macro_rules! forward_ops {
+    ($($ty:ty: $op:ident::$method:ident, $assign:ident::$assign_method:ident;)*) => {$(
+        impl $op for $ty {
+            type Output = $ty;
+
+            fn $method(self, other: $ty) -> $ty {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $assign<&$ty> for $ty {
+            fn $assign_method(&mut self, other: &$ty) {
+                *self = (&*self).$method(other);
+            }
+        }
+    )*};
+}
+
+forward_ops! {
+    BigUint: Add::add, AddAssign::add_assign;
+    BigUint: Mul::mul, MulAssign::mul_assign;
+    BigInt: Add::add, AddAssign::add_assign;
+    BigInt: Mul::mul, MulAssign::mul_assign;
+}
+
+impl FromStr for BigInt {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.strip_prefix('-') {
+            Some(digits) if !digits.starts_with('+') => Ok(BigInt::new(true, digits.parse()?)),
+            _ => Ok(BigInt::from(s.parse::<BigUint>()?)),
+        }
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.negative, "", &self.magnitude.to_decimal())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, BigUint};
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let s = "340282366920938463463374607431768211456123456789";
+        assert_eq!(big(s).to_string(), s);
+        assert_eq!(big("000").to_string(), "0");
+        assert_eq!(big("1000000000").to_string(), "1000000000");
+        assert_eq!(format!("{:>5}", big("42")), "   42");
+        assert!("12a".parse::<BigUint>().is_err());
+        assert!("".parse::<BigUint>().is_err());
+
+        let n: BigInt = "-98765432109876543210987654321".parse().unwrap();
+        assert_eq!(n.to_string(), "-98765432109876543210987654321");
+        assert!("--1".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let max = BigUint::from(u128::MAX);
+        assert_eq!(max.to_u128(), Some(u128::MAX));
+        let sum = &max + &BigUint::from(1u8);
+        assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(sum.to_u128(), None);
+
+        let product = &max * &max;
+        assert_eq!(
+            product.to_string(),
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025"
+        );
+        assert_eq!(product.div_rem(&max), (max.clone(), BigUint::zero()));
+
+        let (q, r) = big("1000000000000000000000000000007").div_rem(&big("12345678901234567890"));
+        assert_eq!(
+            (q.to_string(), r.to_string()),
+            ("81000000729".to_string(), "81910000008197".to_string())
+        );
+        assert_eq!(big("12345678901234567890").checked_sub(&max), None);
+        assert_eq!(
+            BigUint::from(2u8).pow(100).to_string(),
+            "1267650600228229401496703205376"
+        );
+    }
+
+    #[test]
+    fn gcd_and_lcm() {
+        let a = BigUint::from(2u8).pow(70) * BigUint::from(3u8);
+        let b = BigUint::from(2u8).pow(64) * BigUint::from(5u8);
+        assert_eq!(a.gcd(&b), BigUint::from(2u8).pow(64));
+        assert_eq!(a.lcm(&b), BigUint::from(2u8).pow(70) * BigUint::from(15u8));
+        assert_eq!(a.gcd(&BigUint::zero()), a);
+    }
+
+    #[test]
+    fn signed() {
+        let a = BigInt::from(-7);
+        let b = BigInt::from(3);
+        assert_eq!(&a + &b, BigInt::from(-4));
+        assert_eq!(&b - &a, BigInt::from(10));
+        assert_eq!(&a * &b, BigInt::from(-21));
+        assert_eq!(a.div_rem(&b), (BigInt::from(-2), BigInt::from(-1)));
+        assert_eq!(a.gcd(&b), BigInt::from(1));
+        assert!(a < b);
+        assert_eq!(&a + &BigInt::from(7), BigInt::default());
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(BigInt::from(u128::MAX).to_i128(), None);
+    }
+}
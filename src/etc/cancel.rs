@@ -0,0 +1,51 @@
+use super::Error;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Token for cooperatively cancelling a running solver.
+///
+/// The runner cancels the token when a day exceeds `--timeout`. Solvers with
+/// long running loops should call `cancel.check()?` every now and then, so
+/// they stop instead of running on in the background.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fails with `Error::Timeout` once the token was cancelled.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.is_cancelled() {
+            true => Err(Error::Timeout),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cancel, Error};
+
+    #[test]
+    fn cancel() {
+        let cancel = Cancel::new();
+        let clone = cancel.clone();
+        assert_eq!(clone.check(), Ok(()));
+
+        cancel.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.check(), Err(Error::Timeout));
+    }
+}
@@ -0,0 +1,542 @@
+use super::{
+    bench::{Phase, Runs, DEFAULT_BUDGET},
+    extract::ExtractOptions,
+    fetch::FetchOptions,
+    history::CompareOptions,
+    input::InputSource,
+    parse_selection,
+    report::Format,
+    solver::Part,
+    submit::SubmitOptions,
+    table::Sort,
+};
+use anyhow::{bail, Context, Result};
+use std::{path::PathBuf, time::Duration};
+
+pub const USAGE: &str = "\
+Usage: advent_of_code_template [OPTIONS] <DAYS>...
+       advent_of_code_template compare [--baseline <NAME>] [--threshold <PCT>] [--phase <PHASE>]
+       advent_of_code_template fetch [--input-dir <DIR>] [--base-url <URL>] <DAYS>...
+       advent_of_code_template submit [--input <PATH>] [--base-url <URL>] <DAY> <PART>
+       advent_of_code_template extract [--page <PATH>] [--base-url <URL>] <DAY>
+
+Days:
+  all, latest, 7, 1-9, 1,3,7, !12
+
+Options:
+  --input <PATH>      Read the input of the (single) selected day from PATH, `-` for stdin
+  --input-dir <DIR>   Read the inputs from DIR/dayNN.txt instead of input/
+  --examples          Run the examples in input/test/dayNN[_XX].txt and check them against
+                      the expected answers in input/test/dayNN[_XX].answers
+  --part <PART>       Only solve part 1 or 2
+  --bench [N]         Benchmark every day with N timed runs, or for about a second each,
+                      and append the results to .aoc/timings.jsonl. `--bench=N` works too
+  --bench-time <SECS> Benchmark every day for about SECS seconds instead of one
+  --tag <NAME>        Name the benchmark run, to use it as a baseline in `compare`
+  --format <FORMAT>   Output format: text (default), json or csv
+  --sort <ORDER>      Order of the summary table: day (default) or time, slowest first
+  -j, --jobs <N>      Run N days in parallel (default 1)
+  --timeout <SECS>    Cancel a day after SECS seconds and report it as TIMEOUT
+  --record            Store the answers in answers/dayNN.txt as the new known-good ones
+  -h, --help          Print this help
+
+Compare:
+  Compares the latest benchmark run with the previous one.
+  --baseline <NAME>   Compare with the latest run tagged NAME or at commit NAME instead
+  --threshold <PCT>   Flag days that got more than PCT percent slower (default 10)
+  --phase <PHASE>     Compare parse, part1, part2 or total (default)
+
+Fetch:
+  Downloads the puzzle inputs that are not in input/ yet. The session cookie is read from
+  the COOKIE environment variable or `session = ...` in .aoc/config.
+  --input-dir <DIR>   Write the inputs to DIR/dayNN.txt instead of input/
+  --base-url <URL>    Fetch from URL instead of https://adventofcode.com
+
+Submit:
+  Solves the part and submits the answer. Answers that were rejected before, or that lie
+  outside the bounds of earlier too high and too low answers, are not sent. Every reply is
+  logged in .aoc/submissions/dayNN.jsonl. Exits with 0 for a correct answer, 2 for a wrong
+  one and 10 when the website asks to wait before submitting again.
+  --input <PATH>      Solve the input in PATH instead of input/dayNN.txt
+  --base-url <URL>    Submit to URL instead of https://adventofcode.com
+
+Extract:
+  Writes every <pre><code> block of the puzzle page to input/test/dayNN_XX.txt, next to a
+  dayNN_XX.answers file offering the emphasised numbers of its part as expected answers.
+  Existing examples are kept and blocks that already are an example of the day are skipped.
+  --page <PATH>       Read a saved puzzle page instead of fetching it
+  --base-url <URL>    Fetch from URL instead of https://adventofcode.com";
+
+/// What the binary was asked to do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run(Options),
+    Compare(CompareOptions),
+    Fetch(FetchOptions),
+    Submit(SubmitOptions),
+    Extract(ExtractOptions),
+    Help,
+}
+
+impl Command {
+    /// Parses the command line arguments, without the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut args = args.into_iter().peekable();
+        match args.peek().map(String::as_str) {
+            None | Some("help") => Ok(Command::Help),
+            Some("compare") => {
+                args.next();
+                parse_compare(args)
+            }
+            Some("fetch") => {
+                args.next();
+                parse_fetch(args)
+            }
+            Some("submit") => {
+                args.next();
+                parse_submit(args)
+            }
+            Some("extract") => {
+                args.next();
+                parse_extract(args)
+            }
+            _ => Ok(Options::parse(args)?.map_or(Command::Help, Command::Run)),
+        }
+    }
+}
+
+fn parse_compare<I: Iterator<Item = String>>(mut args: I) -> Result<Command> {
+    let mut options = CompareOptions {
+        baseline: None,
+        threshold: 10.0,
+        phase: Phase::Total,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--baseline" => options.baseline = Some(value(&arg, args.next())?),
+            "--threshold" => {
+                let pct = value(&arg, args.next())?;
+                options.threshold = match pct.trim_end_matches('%').parse::<f64>() {
+                    Ok(pct) if pct >= 0.0 => pct,
+                    _ => bail!("Invalid threshold '{pct}', expected a percentage."),
+                }
+            }
+            "--phase" => {
+                let phase = value(&arg, args.next())?;
+                options.phase = Phase::from_name(&phase).with_context(|| {
+                    format!("Unknown phase '{phase}', expected parse, part1, part2 or total.")
+                })?;
+            }
+            _ => bail!("Unknown argument '{arg}' for compare, see --help."),
+        }
+    }
+    Ok(Command::Compare(options))
+}
+
+fn parse_fetch<I: Iterator<Item = String>>(mut args: I) -> Result<Command> {
+    let mut selection = vec![];
+    let mut dir = None;
+    let mut base_url = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--input-dir" => dir = Some(PathBuf::from(value(&arg, args.next())?)),
+            "--base-url" => base_url = Some(value(&arg, args.next())?),
+            _ if arg.starts_with("--") => bail!("Unknown argument '{arg}' for fetch, see --help."),
+            _ => selection.push(arg),
+        }
+    }
+    if selection.is_empty() {
+        bail!("Please provide the day(s) to fetch.");
+    }
+    let mut options = FetchOptions::new(parse_selection(&selection)?);
+    options.dir = dir.unwrap_or(options.dir);
+    options.base_url = base_url;
+    Ok(Command::Fetch(options))
+}
+
+fn parse_submit<I: Iterator<Item = String>>(mut args: I) -> Result<Command> {
+    let mut positional = vec![];
+    let mut input = InputSource::Default;
+    let mut base_url = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--input" => input = InputSource::File(PathBuf::from(value(&arg, args.next())?)),
+            "--base-url" => base_url = Some(value(&arg, args.next())?),
+            _ if arg.starts_with("--") => bail!("Unknown argument '{arg}' for submit, see --help."),
+            _ => positional.push(arg),
+        }
+    }
+    let [day, part] = positional.as_slice() else {
+        bail!("Please provide the day and the part to submit.");
+    };
+    let day = match parse_selection(&[day])?.as_slice() {
+        &[day] => day,
+        _ => bail!("Only a single day can be submitted, got '{day}'."),
+    };
+    Ok(Command::Submit(SubmitOptions {
+        day,
+        part: part.parse()?,
+        input,
+        base_url,
+    }))
+}
+
+fn parse_extract<I: Iterator<Item = String>>(mut args: I) -> Result<Command> {
+    let mut positional = vec![];
+    let mut page = None;
+    let mut base_url = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--page" => page = Some(PathBuf::from(value(&arg, args.next())?)),
+            "--base-url" => base_url = Some(value(&arg, args.next())?),
+            _ if arg.starts_with("--") => {
+                bail!("Unknown argument '{arg}' for extract, see --help.")
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [day] = positional.as_slice() else {
+        bail!("Please provide the day to extract the examples of.");
+    };
+    let day = match parse_selection(&[day])?.as_slice() {
+        &[day] => day,
+        _ => bail!("Only a single day can be extracted, got '{day}'."),
+    };
+    Ok(Command::Extract(ExtractOptions {
+        day,
+        page,
+        base_url,
+    }))
+}
+
+/// Options of a run, parsed from the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub days: Vec<u8>,
+    pub input: InputSource,
+    /// Run the examples in `input/test` instead of the puzzle inputs.
+    pub examples: bool,
+    pub bench: Option<Runs>,
+    pub format: Format,
+    /// Order of the rows in the summary table.
+    pub sort: Sort,
+    /// Overwrite the known-good answers with the current ones.
+    pub record: bool,
+    /// Number of days run in parallel.
+    pub jobs: usize,
+    /// Wall-clock limit per day, including benchmark runs.
+    pub timeout: Option<Duration>,
+    /// Only solve this part, both parts if `None`.
+    pub part: Option<Part>,
+    /// Name of the benchmark run in the timing history.
+    pub tag: Option<String>,
+}
+
+impl Options {
+    /// Parses the command line arguments, without the program name.
+    ///
+    /// Returns `Ok(None)` if help was requested.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Self>> {
+        let mut args = args.into_iter().peekable();
+        let mut selection = vec![];
+        let mut input = InputSource::Default;
+        let mut examples = false;
+        let mut bench = None;
+        let mut budget = None;
+        let mut format = Format::Text;
+        let mut sort = Sort::Day;
+        let mut record = false;
+        let mut jobs = 1;
+        let mut timeout = None;
+        let mut part = None;
+        let mut tag = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--input" => {
+                    input = match value(&arg, args.next())?.as_str() {
+                        "-" => InputSource::Stdin,
+                        path => InputSource::File(PathBuf::from(path)),
+                    }
+                }
+                "--input-dir" => input = InputSource::Dir(PathBuf::from(value(&arg, args.next())?)),
+                "--examples" => examples = true,
+                "--format" => format = value(&arg, args.next())?.parse()?,
+                "--sort" => sort = value(&arg, args.next())?.parse()?,
+                "--record" => record = true,
+                "--tag" => tag = Some(value(&arg, args.next())?),
+                "--part" => part = Some(value(&arg, args.next())?.parse()?),
+                "--timeout" => {
+                    let secs = value(&arg, args.next())?;
+                    match secs.parse::<f64>() {
+                        Ok(secs) if secs > 0.0 && secs.is_finite() => {
+                            timeout = Some(Duration::from_secs_f64(secs))
+                        }
+                        _ => bail!("Invalid timeout '{secs}', expected a number of seconds."),
+                    }
+                }
+                "-j" | "--jobs" => {
+                    let n = value(&arg, args.next())?;
+                    jobs = match n.parse() {
+                        Ok(jobs) if jobs > 0 => jobs,
+                        _ => bail!("Invalid number of jobs '{n}'."),
+                    }
+                }
+                "--bench" => {
+                    // A number right after `--bench` is the number of runs, not a day.
+                    bench = Some(match args.next_if(|next| next.parse::<u32>().is_ok()) {
+                        Some(runs) => parse_runs(&runs)?,
+                        None => Runs::Budget(DEFAULT_BUDGET),
+                    })
+                }
+                _ if arg.starts_with("--bench=") => {
+                    bench = Some(parse_runs(&arg["--bench=".len()..])?)
+                }
+                "--bench-time" => {
+                    let secs = value(&arg, args.next())?;
+                    match secs.parse::<f64>() {
+                        Ok(secs) if secs > 0.0 && secs.is_finite() => {
+                            budget = Some(Duration::from_secs_f64(secs))
+                        }
+                        _ => {
+                            bail!("Invalid benchmark time '{secs}', expected a number of seconds.")
+                        }
+                    }
+                }
+                _ if arg.starts_with("--") => bail!("Unknown option '{arg}', see --help."),
+                _ => selection.push(arg),
+            }
+        }
+
+        let bench = match (bench, budget) {
+            (Some(Runs::Count(_)), Some(_)) => {
+                bail!("`--bench-time` cannot be combined with a number of benchmark runs.")
+            }
+            (_, Some(budget)) => Some(Runs::Budget(budget)),
+            (bench, None) => bench,
+        };
+
+        if selection.is_empty() {
+            bail!("Please provide the day(s) to run.");
+        }
+        let days = parse_selection(&selection)?;
+        if input.is_single() && days.len() != 1 {
+            bail!(
+                "`--input` can only be used with a single day, but {} are selected.",
+                days.len()
+            );
+        }
+
+        if examples && input != InputSource::Default {
+            bail!("`--examples` reads the inputs from input/test, it cannot be combined with `--input` or `--input-dir`.");
+        }
+
+        Ok(Some(Options {
+            days,
+            input,
+            examples,
+            bench,
+            format,
+            sort,
+            record,
+            jobs,
+            timeout,
+            part,
+            tag,
+        }))
+    }
+}
+
+fn parse_runs(runs: &str) -> Result<Runs> {
+    match runs.parse() {
+        Ok(n) if n > 0 => Ok(Runs::Count(n)),
+        _ => bail!("Invalid number of benchmark runs '{runs}'."),
+    }
+}
+
+fn value(option: &str, value: Option<String>) -> Result<String> {
+    value.with_context(|| format!("Option '{option}' expects a value."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Command, Format, InputSource, Options, Part, Phase, Runs, Sort, DEFAULT_BUDGET};
+    use std::{path::PathBuf, time::Duration};
+
+    fn parse(args: &[&str]) -> anyhow::Result<Option<Options>> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn input_options() {
+        let options = parse(&["3", "--input", "-"]).unwrap().unwrap();
+        assert_eq!(options.input, InputSource::Stdin);
+
+        let options = parse(&["--input-dir", "inputs/bob", "all"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.input, InputSource::Dir(PathBuf::from("inputs/bob")));
+    }
+
+    #[test]
+    fn examples() {
+        assert!(!parse(&["3"]).unwrap().unwrap().examples);
+        assert!(parse(&["--examples", "3"]).unwrap().unwrap().examples);
+        assert!(parse(&["--examples", "3", "--input", "day03.txt"]).is_err());
+    }
+
+    #[test]
+    fn bench() {
+        let options = parse(&["1", "--bench"]).unwrap().unwrap();
+        assert_eq!(options.bench, Some(Runs::Budget(DEFAULT_BUDGET)));
+
+        let options = parse(&["--bench=50", "1"]).unwrap().unwrap();
+        assert_eq!(options.bench, Some(Runs::Count(50)));
+        assert_eq!(options.days, vec![1]);
+
+        let options = parse(&["--bench", "50", "1-3"]).unwrap().unwrap();
+        assert_eq!(options.bench, Some(Runs::Count(50)));
+        assert_eq!(options.days, vec![1, 2, 3]);
+
+        let options = parse(&["--bench", "1-3"]).unwrap().unwrap();
+        assert_eq!(options.bench, Some(Runs::Budget(DEFAULT_BUDGET)));
+        assert_eq!(options.days, vec![1, 2, 3]);
+
+        let options = parse(&["1", "--bench-time", "0.5"]).unwrap().unwrap();
+        assert_eq!(
+            options.bench,
+            Some(Runs::Budget(Duration::from_millis(500)))
+        );
+
+        assert!(parse(&["1", "--bench=0"]).is_err());
+        assert!(parse(&["1", "--bench", "0"]).is_err());
+        assert!(parse(&["1", "--bench=5", "--bench-time", "2"]).is_err());
+    }
+
+    #[test]
+    fn format() {
+        let options = parse(&["1", "--format", "csv"]).unwrap().unwrap();
+        assert_eq!(options.format, Format::Csv);
+        assert!(parse(&["1", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn sort() {
+        assert_eq!(parse(&["all"]).unwrap().unwrap().sort, Sort::Day);
+        let options = parse(&["all", "--sort", "time"]).unwrap().unwrap();
+        assert_eq!(options.sort, Sort::Time);
+        assert!(parse(&["all", "--sort", "name"]).is_err());
+    }
+
+    #[test]
+    fn jobs() {
+        assert_eq!(parse(&["all"]).unwrap().unwrap().jobs, 1);
+        assert_eq!(parse(&["all", "-j", "8"]).unwrap().unwrap().jobs, 8);
+        assert!(parse(&["all", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn timeout() {
+        let options = parse(&["all", "--timeout", "1.5"]).unwrap().unwrap();
+        assert_eq!(options.timeout, Some(Duration::from_millis(1500)));
+        assert!(parse(&["all", "--timeout", "-1"]).is_err());
+    }
+
+    #[test]
+    fn part() {
+        assert_eq!(parse(&["all"]).unwrap().unwrap().part, None);
+        let options = parse(&["all", "--part", "2"]).unwrap().unwrap();
+        assert_eq!(options.part, Some(Part::Two));
+        assert!(parse(&["all", "--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn compare() {
+        let command = Command::parse(
+            [
+                "compare",
+                "--baseline",
+                "v1",
+                "--threshold",
+                "5%",
+                "--phase",
+                "part2",
+            ]
+            .map(String::from),
+        )
+        .unwrap();
+        let Command::Compare(options) = command else {
+            panic!("expected compare, got {command:?}");
+        };
+        assert_eq!(options.baseline.as_deref(), Some("v1"));
+        assert_eq!(options.threshold, 5.0);
+        assert_eq!(options.phase, Phase::Part2);
+
+        assert!(Command::parse(["compare", "--phase", "part3"].map(String::from)).is_err());
+        assert!(matches!(
+            Command::parse(["1", "--tag", "v1"].map(String::from)).unwrap(),
+            Command::Run(Options { tag: Some(_), .. })
+        ));
+    }
+
+    #[test]
+    fn fetch() {
+        let command = Command::parse(
+            ["fetch", "1-3", "--base-url", "http://localhost:8080"].map(String::from),
+        )
+        .unwrap();
+        let Command::Fetch(options) = command else {
+            panic!("expected fetch, got {command:?}");
+        };
+        assert_eq!(options.days, vec![1, 2, 3]);
+        assert_eq!(options.dir, PathBuf::from("input"));
+        assert_eq!(options.base_url.as_deref(), Some("http://localhost:8080"));
+
+        assert!(Command::parse(["fetch"].map(String::from)).is_err());
+        assert!(Command::parse(["fetch", "1", "--record"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn submit() {
+        let command = Command::parse(["submit", "7", "2"].map(String::from)).unwrap();
+        let Command::Submit(options) = command else {
+            panic!("expected submit, got {command:?}");
+        };
+        assert_eq!((options.day, options.part), (7, Part::Two));
+        assert_eq!(options.input, InputSource::Default);
+
+        assert!(Command::parse(["submit", "7"].map(String::from)).is_err());
+        assert!(Command::parse(["submit", "1-3", "1"].map(String::from)).is_err());
+        assert!(Command::parse(["submit", "7", "3"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn extract() {
+        let command =
+            Command::parse(["extract", "--page", "day07.html", "7"].map(String::from)).unwrap();
+        let Command::Extract(options) = command else {
+            panic!("expected extract, got {command:?}");
+        };
+        assert_eq!(options.day, 7);
+        assert_eq!(options.page, Some(PathBuf::from("day07.html")));
+
+        assert!(Command::parse(["extract"].map(String::from)).is_err());
+        assert!(Command::parse(["extract", "all"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(&["1-3", "--input", "day01.txt"]).is_err());
+        assert!(parse(&["1", "--input"]).is_err());
+        assert!(parse(&["1", "--frobnicate"]).is_err());
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--help"]).unwrap().is_none());
+        assert_eq!(Command::parse([]).unwrap(), Command::Help);
+        assert_eq!(Command::parse(["help".to_string()]).unwrap(), Command::Help);
+    }
+}
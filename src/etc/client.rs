@@ -0,0 +1,275 @@
+use anyhow::{bail, Context, Result};
+use std::{env, fs, path::Path, time::Duration};
+
+/// Settings for talking to the Advent of Code website, stored as
+///
+/// ```text
+/// session = 53616c7465645f5f...
+/// year = 2023
+/// base_url = https://adventofcode.com
+/// ```
+///
+/// Every key is optional, blank lines and lines starting with `#` are ignored.
+pub const CONFIG_PATH: &str = ".aoc/config";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u16 = 2023;
+/// Environment variable holding the session cookie, it takes precedence over the config file.
+pub const COOKIE_VAR: &str = "COOKIE";
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub year: u16,
+    pub base_url: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            year: DEFAULT_YEAR,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `CONFIG_PATH` if it exists, with the session from `COOKIE_VAR` if set.
+    pub fn load() -> Result<Self> {
+        let path = Path::new(CONFIG_PATH);
+        let mut config = match path.is_file() {
+            true => {
+                let text = fs::read_to_string(path)
+                    .with_context(|| format!("Could not read '{CONFIG_PATH}'."))?;
+                Config::parse(&text).with_context(|| format!("Invalid config '{CONFIG_PATH}'."))?
+            }
+            false => Config::default(),
+        };
+        if let Some(session) = env::var(COOKIE_VAR).ok().filter(|s| !s.trim().is_empty()) {
+            config.session = Some(session.trim().to_string());
+        }
+        Ok(config)
+    }
+
+    /// Like `load`, with `base_url` overriding the one of the config if given,
+    /// as the `--base-url` option of the subcommands does.
+    pub fn load_with(base_url: Option<&str>) -> Result<Self> {
+        let mut config = Config::load()?;
+        if let Some(base_url) = base_url {
+            config.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        Ok(config)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config = Config::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                bail!("Invalid line '{line}', expected 'key = value'.");
+            };
+            let value = value.trim();
+            match key.trim() {
+                "session" => config.session = Some(value.to_string()),
+                "year" => {
+                    config.year = value
+                        .parse()
+                        .with_context(|| format!("Invalid year '{value}'."))?
+                }
+                "base_url" => config.base_url = value.trim_end_matches('/').to_string(),
+                key => bail!("Unknown key '{key}'."),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// HTTP client for the puzzle pages of one year, authenticated with the session cookie.
+pub struct Client {
+    agent: ureq::Agent,
+    cookie: String,
+    base_url: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self> {
+        let Some(session) = &config.session else {
+            bail!("No session cookie, set the {COOKIE_VAR} environment variable or `session` in {CONFIG_PATH}.");
+        };
+        Ok(Client {
+            agent: ureq::AgentBuilder::new()
+                .timeout(TIMEOUT)
+                .user_agent(USER_AGENT)
+                .build(),
+            cookie: format!("session={session}"),
+            base_url: format!("{}/{}", config.base_url, config.year),
+        })
+    }
+
+    /// URL of `path` below the year, e.g. `day/1/input`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.base_url)
+    }
+
+    /// Fetches `path` below the year, failing on any status other than 200.
+    pub fn get(&self, path: &str) -> Result<String> {
+        let url = self.url(path);
+        let request = self.agent.get(&url).set("Cookie", &self.cookie);
+        read_response(&url, request.call())
+    }
+
+    /// Posts the form `fields` to `path` below the year.
+    pub fn post(&self, path: &str, fields: &[(&str, &str)]) -> Result<String> {
+        let url = self.url(path);
+        let request = self.agent.post(&url).set("Cookie", &self.cookie);
+        read_response(&url, request.send_form(fields))
+    }
+}
+
+fn read_response(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .with_context(|| format!("Could not read the response of {url}.")),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            // Skip the markup of HTML error pages, the website explains errors in plain text.
+            let reason = body
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with('<'));
+            match reason {
+                Some(reason) => bail!("{url} answered with status {status}: {reason}"),
+                None => bail!("{url} answered with status {status}."),
+            }
+        }
+        Err(err) => Err(err).with_context(|| format!("Could not reach {url}.")),
+    }
+}
+
+/// A stand-in for the website in tests, serving canned responses on a local port.
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Answers every request with `handler(request) -> (status, body)`.
+    pub struct Server {
+        pub base_url: String,
+        requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl Server {
+        pub fn start(handler: fn(&Request) -> (u16, String)) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(vec![]));
+            let log = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming().map_while(Result::ok) {
+                    let request = read_request(&stream);
+                    let (status, body) = handler(&request);
+                    log.lock().unwrap().push(request);
+                    write!(
+                        &stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            });
+            Server { base_url, requests }
+        }
+
+        /// The requests received so far.
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_request(stream: &TcpStream) -> Request {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut words = line.split_whitespace();
+        let method = words.next().unwrap_or_default().to_string();
+        let path = words.next().unwrap_or_default().to_string();
+
+        let (mut cookie, mut length) = (None, 0);
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let Some((name, value)) = line.trim_end().split_once(": ") else {
+                break;
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "cookie" => cookie = Some(value.to_string()),
+                "content-length" => length = value.parse().unwrap(),
+                _ => {}
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        Request {
+            method,
+            path,
+            cookie,
+            body: String::from_utf8(body).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Client, Config, DEFAULT_BASE_URL};
+
+    #[test]
+    fn parse_config() {
+        let config = Config::parse(
+            "# adventofcode.com\nsession = abc123\n\nyear=2022\nbase_url = http://localhost:8080/\n",
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.base_url, "http://localhost:8080");
+
+        assert_eq!(Config::parse("").unwrap().base_url, DEFAULT_BASE_URL);
+        assert!(Config::parse("year = twenty").is_err());
+        assert!(Config::parse("token = abc").is_err());
+        assert!(Config::parse("session").is_err());
+
+        let config = Config::load_with(Some("http://localhost:8080/")).unwrap();
+        assert_eq!(config.base_url, "http://localhost:8080");
+    }
+
+    #[test]
+    fn needs_session() {
+        assert!(Client::new(&Config::default()).is_err());
+        let config = Config {
+            session: Some("abc".to_string()),
+            ..Config::default()
+        };
+        let client = Client::new(&config).unwrap();
+        assert_eq!(
+            client.url("day/1/input"),
+            "https://adventofcode.com/2023/day/1/input"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
+
+/// Why a day failed.
+///
+/// Solvers return `anyhow::Result`, the runner looks for an `Error` in
+/// there and treats anything else as `Internal`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input of the day does not exist, `path` is `None` for embedded inputs.
+    InputMissing { day: u8, path: Option<PathBuf> },
+    /// The input could not be parsed, `line` and `column` start at 1.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        message: String,
+        /// The offending line, filled in by the runner.
+        text: String,
+    },
+    /// The part is not solved yet.
+    Unsolved,
+    /// The day was cancelled because it ran into `--timeout`.
+    Timeout,
+    /// Any other error.
+    Internal(String),
+}
+
+impl Error {
+    /// A parse error in `line` at `column` of the day's input.
+    pub fn parse<S: Into<String>>(line: usize, column: usize, message: S) -> Self {
+        Error::Parse {
+            day: 0,
+            line,
+            column,
+            message: message.into(),
+            text: String::new(),
+        }
+    }
+
+    /// Finds the typed error in `err`, wrapping untyped ones as `Internal`.
+    pub fn from_anyhow(err: &anyhow::Error) -> Self {
+        match err.downcast_ref::<Error>() {
+            Some(error) => error.clone(),
+            None => Error::Internal(format!("{err:#}")),
+        }
+    }
+
+    /// Adds the day and the offending line of `input` to a parse error.
+    pub fn with_input(self, day: u8, input: &str) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => Error::Parse {
+                day,
+                line,
+                column,
+                message,
+                text: input
+                    .lines()
+                    .nth(line.saturating_sub(1))
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InputMissing {
+                day,
+                path: Some(path),
+            } => write!(
+                f,
+                "The input for day {day} is missing, expected '{}'.",
+                path.display()
+            ),
+            Error::InputMissing { day, path: None } => write!(
+                f,
+                "The input for day {day} is missing, it was not embedded at build time."
+            ),
+            Error::Parse {
+                line,
+                column,
+                message,
+                text,
+                ..
+            } => {
+                write!(f, "Parse error on line {line}, column {column}: {message}")?;
+                if !text.is_empty() {
+                    let number = line.to_string();
+                    let pad = " ".repeat(number.len());
+                    let caret = " ".repeat(column.saturating_sub(1));
+                    write!(f, "\n{number} | {text}\n{pad} | {caret}^")?;
+                }
+                Ok(())
+            }
+            Error::Unsolved => f.write_str("This part is not solved yet."),
+            Error::Timeout => f.write_str("Cancelled after the timeout."),
+            Error::Internal(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses every line of `input` with `f`.
+///
+/// Errors of `f` become parse errors on that line, at the column of a parse
+/// error returned by `f` or at the start of the line otherwise.
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> anyhow::Result<Vec<T>>
+where
+    F: FnMut(&str) -> anyhow::Result<T>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            f(line).map_err(|err| {
+                let error = match Error::from_anyhow(&err) {
+                    Error::Parse {
+                        column, message, ..
+                    } => Error::parse(i + 1, column, message),
+                    _ => Error::parse(i + 1, 1, format!("{err:#}")),
+                };
+                anyhow::Error::new(error)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_lines, Error};
+    use anyhow::anyhow;
+
+    #[test]
+    fn caret() {
+        let error = Error::parse(2, 5, "unknown colour 'grean'").with_input(2, "a\nGame grean\n");
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 2, column 5: unknown colour 'grean'\n\
+             2 | Game grean\n\
+             \x20 |     ^"
+        );
+    }
+
+    #[test]
+    fn from_anyhow() {
+        let err = anyhow::Error::new(Error::Unsolved).context("part 1");
+        assert_eq!(Error::from_anyhow(&err), Error::Unsolved);
+
+        let err = anyhow!("boom").context("part 1");
+        assert_eq!(
+            Error::from_anyhow(&err),
+            Error::Internal("part 1: boom".to_string())
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let parsed = parse_lines("1\n2\nx3", |line| match line.starts_with('x') {
+            true => Err(Error::parse(0, 2, "unexpected x").into()),
+            false => Ok(line.parse::<u32>()?),
+        });
+        let err = parsed.unwrap_err();
+        assert_eq!(Error::from_anyhow(&err), Error::parse(3, 2, "unexpected x"));
+
+        let err = parse_lines("1\ny", |line| Ok(line.parse::<u32>()?)).unwrap_err();
+        assert!(matches!(
+            Error::from_anyhow(&err),
+            Error::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+}
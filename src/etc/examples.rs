@@ -0,0 +1,102 @@
+use super::{answers::Known, solver::Part};
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Directory holding the puzzle examples as `dayNN.txt` or `dayNN_XX.txt`.
+pub const DIR: &str = "input/test";
+/// Extension of the file next to an example holding its expected answers.
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+/// An example input of a day together with its expected answers.
+///
+/// The answers are stored next to the input, `day03_01.txt` is checked
+/// against `day03_01.answers`, in the same format as `answers/dayNN.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// File name without extension, e.g. `day03_01`.
+    pub name: String,
+    pub input: PathBuf,
+    pub answers: PathBuf,
+}
+
+impl Example {
+    /// The expected answers, all unknown if there is no answers file.
+    pub fn expected(&self) -> Result<Known> {
+        Known::load_file(&self.answers)
+    }
+}
+
+/// The only part an example has an expected answer for.
+///
+/// Puzzles often give different examples for the two parts, such an example
+/// only runs the part it has an answer for.
+pub fn only_part(expected: &Known) -> Option<Part> {
+    match (&expected.part1, &expected.part2) {
+        (Some(_), None) => Some(Part::One),
+        (None, Some(_)) => Some(Part::Two),
+        _ => None,
+    }
+}
+
+/// Finds the examples of `day` in `dir`, sorted by name.
+pub fn find(day: u8, dir: &Path) -> Result<Vec<Example>> {
+    let prefix = format!("day{day:02}");
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) if !dir.exists() => return Ok(vec![]),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Could not read '{}'.", dir.display()))
+        }
+    };
+
+    let mut examples = vec![];
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("Could not read '{}'.", dir.display()))?
+            .path();
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let is_example = path.extension().is_some_and(|ext| ext == "txt")
+            && (name == prefix
+                || name
+                    .strip_prefix(&prefix)
+                    .is_some_and(|s| s.starts_with('_')));
+        if is_example {
+            examples.push(Example {
+                name: name.to_string(),
+                answers: path.with_extension(ANSWERS_EXTENSION),
+                input: path,
+            });
+        }
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, DIR};
+    use std::path::Path;
+
+    #[test]
+    fn finds_examples() {
+        let names = |day| -> Vec<String> {
+            find(day, Path::new(DIR))
+                .unwrap()
+                .into_iter()
+                .map(|example| example.name)
+                .collect()
+        };
+        assert_eq!(names(1), ["day01"]);
+        assert_eq!(names(3), ["day03_01", "day03_02"]);
+        assert!(names(25).is_empty());
+
+        let example = &find(3, Path::new(DIR)).unwrap()[1];
+        assert_eq!(example.answers, Path::new(DIR).join("day03_02.answers"));
+        assert_eq!(example.expected().unwrap().part1.as_deref(), Some("925"));
+    }
+}
@@ -0,0 +1,270 @@
+use super::{
+    client::{Client, Config},
+    examples::{self, ANSWERS_EXTENSION},
+    solver::Part,
+};
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+static RE_CODE_BLOCK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap());
+static RE_ARTICLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+static RE_EMPHASIS: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<em[^>]*>(.*?)</em>").unwrap());
+static RE_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+/// Options of the `extract` subcommand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractOptions {
+    pub day: u8,
+    /// A saved puzzle page, the page is fetched if `None`.
+    pub page: Option<PathBuf>,
+    /// Overrides `base_url` of the config when fetching the page.
+    pub base_url: Option<String>,
+}
+
+/// A `<pre><code>` block of a puzzle page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// The part whose description contains the block.
+    pub part: Part,
+    /// The block as plain text.
+    pub text: String,
+}
+
+/// The `<pre><code>` blocks of the descriptions of a puzzle page.
+pub fn code_blocks(page: &str) -> Vec<Block> {
+    RE_ARTICLE
+        .captures_iter(page)
+        .zip(Part::ALL)
+        .flat_map(|(article, part)| {
+            RE_CODE_BLOCK
+                .captures_iter(&article[1])
+                .map(|caps| Block {
+                    part,
+                    text: text(&caps[1]),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The emphasised numbers of each part's description, outside of the code
+/// blocks and without duplicates.
+///
+/// The puzzles emphasise the answer of their example, usually as the last
+/// such number of the part.
+pub fn candidates(page: &str) -> Vec<Vec<String>> {
+    RE_ARTICLE
+        .captures_iter(page)
+        .map(|article| {
+            let prose = RE_CODE_BLOCK.replace_all(&article[1], "");
+            let mut numbers: Vec<String> = vec![];
+            for caps in RE_EMPHASIS.captures_iter(&prose) {
+                let number = text(&caps[1]).trim().to_string();
+                let is_number = number.strip_prefix('-').unwrap_or(&number);
+                if !is_number.is_empty()
+                    && is_number.chars().all(|c| c.is_ascii_digit())
+                    && !numbers.contains(&number)
+                {
+                    numbers.push(number);
+                }
+            }
+            numbers
+        })
+        .collect()
+}
+
+/// Strips the markup of an HTML fragment and decodes its entities.
+fn text(html: &str) -> String {
+    RE_TAG
+        .replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Answers file offering the candidates of `part`, commented out so nothing
+/// is checked until the right one is uncommented.
+pub fn sidecar(part: Part, numbers: &[String]) -> String {
+    let mut sidecar =
+        format!("# Emphasised numbers of part {part}, uncomment the expected answer.\n");
+    if let Some(last) = numbers.last() {
+        writeln!(
+            sidecar,
+            "# Candidates for part {part}: {}",
+            numbers.join(", ")
+        )
+        .unwrap();
+        writeln!(sidecar, "# part{part}: {last}").unwrap();
+    }
+    sidecar
+}
+
+/// Writes the code blocks of `page` as `dayNN_XX.txt` into `dir`, each with
+/// an answers file offering the candidates of the part it belongs to.
+///
+/// Existing files are kept, and blocks that are already an example of the
+/// day, e.g. a `dayNN.txt` written by hand before, are skipped.
+///
+/// Returns the paths of the written examples.
+pub fn extract(day: u8, page: &str, dir: &Path) -> Result<Vec<PathBuf>> {
+    let blocks = code_blocks(page);
+    if blocks.is_empty() {
+        bail!("The page of day {day} contains no <pre><code> block.");
+    }
+    let candidates = candidates(page);
+    let mut existing = vec![];
+    for example in examples::find(day, dir)? {
+        let input = fs::read_to_string(&example.input)
+            .with_context(|| format!("Could not read '{}'.", example.input.display()))?;
+        existing.push(input.trim_end().to_string());
+    }
+
+    fs::create_dir_all(dir).with_context(|| format!("Could not create '{}'.", dir.display()))?;
+    let mut written = vec![];
+    for (i, block) in blocks.iter().enumerate() {
+        let input = dir.join(format!("day{day:02}_{:02}.txt", i + 1));
+        if input.exists() || existing.contains(&block.text.trim_end().to_string()) {
+            continue;
+        }
+        fs::write(&input, &block.text)
+            .with_context(|| format!("Could not write '{}'.", input.display()))?;
+        let answers = input.with_extension(ANSWERS_EXTENSION);
+        if !answers.exists() {
+            let numbers = candidates
+                .get(block.part.index())
+                .map_or(&[][..], Vec::as_slice);
+            fs::write(&answers, sidecar(block.part, numbers))
+                .with_context(|| format!("Could not write '{}'.", answers.display()))?;
+        }
+        written.push(input);
+    }
+    Ok(written)
+}
+
+/// Extracts the examples of the selected day and prints what was written.
+pub fn run(options: &ExtractOptions) -> Result<()> {
+    let day = options.day;
+    let page = match &options.page {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Could not read '{}'.", path.display()))?,
+        None => {
+            let config = Config::load_with(options.base_url.as_deref())?;
+            Client::new(&config)?.get(&format!("day/{day}"))?
+        }
+    };
+
+    let dir = Path::new(examples::DIR);
+    let written = extract(day, &page, dir)?;
+    for path in &written {
+        println!("Wrote {}", path.display());
+    }
+    if written.is_empty() {
+        println!(
+            "Every example of day {day} is already in {}.",
+            dir.display()
+        );
+    }
+    for (i, numbers) in candidates(&page).iter().enumerate().take(2) {
+        if numbers.is_empty() {
+            continue;
+        }
+        println!("Candidates for part {}: {}", i + 1, numbers.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{candidates, code_blocks, extract, sidecar, Block};
+    use crate::etc::{answers::Known, solver::Part, temp::TempDir};
+    use std::fs;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>It looks like <em>some</em> of the digits are actually <em>spelled out with letters</em>:</p>
+<pre><code>two1nine
+x&lt;<em>4</em>&gt;&amp;
+</code></pre>
+<p>Instead of <code><em>142</em></code>, adding these together produces <code><em>281</em></code>.</p>
+<p>What is the sum? <em>281</em></p>
+</article>
+</main>"#;
+
+    #[test]
+    fn parse_page() {
+        assert_eq!(
+            code_blocks(PAGE),
+            [
+                Block {
+                    part: Part::One,
+                    text: "1abc2\npqr3stu8vwx\n".to_string()
+                },
+                Block {
+                    part: Part::Two,
+                    text: "two1nine\nx<4>&\n".to_string()
+                }
+            ]
+        );
+        let candidates = candidates(PAGE);
+        assert_eq!(candidates, [vec!["142"], vec!["142", "281"]]);
+        assert_eq!(
+            sidecar(Part::Two, &candidates[1]),
+            "# Emphasised numbers of part 2, uncomment the expected answer.\n\
+             # Candidates for part 2: 142, 281\n\
+             # part2: 281\n"
+        );
+    }
+
+    #[test]
+    fn writes_examples() {
+        let dir = TempDir::new("extract");
+        fs::write(dir.join("day01_02.txt"), "kept").unwrap();
+
+        let written = extract(1, PAGE, &dir).unwrap();
+        assert_eq!(written, [dir.join("day01_01.txt")]);
+        assert_eq!(
+            fs::read_to_string(dir.join("day01_01.txt")).unwrap(),
+            "1abc2\npqr3stu8vwx\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("day01_02.txt")).unwrap(),
+            "kept"
+        );
+        assert!(!dir.join("day01_02.answers").exists());
+        let known = Known::load_file(&dir.join("day01_01.answers")).unwrap();
+        assert_eq!(known, Known::default());
+        let sidecar = fs::read_to_string(dir.join("day01_01.answers")).unwrap();
+        assert!(sidecar.contains("# part1: 142") && !sidecar.contains("part2"));
+
+        assert!(extract(1, "<p>No examples</p>", &dir).is_err());
+    }
+
+    #[test]
+    fn skips_legacy_examples() {
+        let dir = TempDir::new("extract-legacy");
+        fs::write(dir.join("day01.txt"), "1abc2\npqr3stu8vwx").unwrap();
+
+        let written = extract(1, PAGE, &dir).unwrap();
+        assert_eq!(written, [dir.join("day01_02.txt")]);
+        assert!(!dir.join("day01_01.txt").exists());
+        let sidecar = fs::read_to_string(dir.join("day01_02.answers")).unwrap();
+        assert!(sidecar.contains("# part2: 281") && !sidecar.contains("part1"));
+    }
+}
@@ -0,0 +1,224 @@
+use super::{
+    alloc::format_bytes,
+    client::{Client, Config},
+    input::{self, DEFAULT_DIR},
+};
+use anyhow::{bail, Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Options of the `fetch` subcommand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchOptions {
+    pub days: Vec<u8>,
+    /// Directory the inputs are written to as `dayNN.txt`.
+    pub dir: PathBuf,
+    /// Overrides `base_url` of the config, e.g. to use a mirror.
+    pub base_url: Option<String>,
+}
+
+impl FetchOptions {
+    pub fn new(days: Vec<u8>) -> Self {
+        FetchOptions {
+            days,
+            dir: PathBuf::from(DEFAULT_DIR),
+            base_url: None,
+        }
+    }
+}
+
+/// What happened to the input of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Downloaded, with the size in bytes.
+    Fetched(u64),
+    /// A valid input was already there, nothing was requested.
+    Cached,
+}
+
+/// Downloads the inputs of the selected days and prints what happened to each.
+///
+/// Returns whether every input is now available.
+pub fn run(options: &FetchOptions) -> Result<bool> {
+    let config = Config::load_with(options.base_url.as_deref())?;
+    fetch(&config, options)
+}
+
+/// Like `run`, with the given config. The client, and with it the session, is
+/// only needed once an input is missing.
+fn fetch(config: &Config, options: &FetchOptions) -> Result<bool> {
+    let mut client = None;
+    let mut ok = true;
+    for &day in &options.days {
+        let path = options.dir.join(input::file_name(day));
+        let outcome = match is_cached(&path) {
+            true => Ok(Outcome::Cached),
+            false => {
+                let client = match &client {
+                    Some(client) => client,
+                    None => client.insert(Client::new(config)?),
+                };
+                fetch_day(client, day, &path)
+            }
+        };
+        match outcome {
+            Ok(Outcome::Fetched(bytes)) => println!(
+                "Day {day:02}: fetched {} into {}",
+                format_bytes(bytes),
+                path.display()
+            ),
+            Ok(Outcome::Cached) => println!("Day {day:02}: cached in {}", path.display()),
+            Err(err) => {
+                println!("Day {day:02}: {err:#}");
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Whether `path` holds an input, an empty file or a saved error page does not count.
+pub fn is_cached(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|input| check_input(&input).is_ok())
+}
+
+/// Downloads the input of `day` into `path`, replacing what is there.
+///
+/// Nothing is written if the server answers with an error, so a failed
+/// fetch never replaces an input with an error page.
+pub fn fetch_day(client: &Client, day: u8, path: &Path) -> Result<Outcome> {
+    let input = client.get(&format!("day/{day}/input"))?;
+    check_input(&input)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Could not create '{}'.", dir.display()))?;
+    }
+    // Write next to the target and rename, so an interrupted fetch leaves no partial input.
+    let partial = path.with_extension("txt.part");
+    fs::write(&partial, &input)
+        .and_then(|()| fs::rename(&partial, path))
+        .with_context(|| format!("Could not write '{}'.", path.display()))?;
+    Ok(Outcome::Fetched(input.len() as u64))
+}
+
+/// Rejects bodies that are not a puzzle input, the website answers some
+/// requests, e.g. for days that are not unlocked yet, with an explanation.
+fn check_input(input: &str) -> Result<()> {
+    const ERRORS: [&str; 2] = [
+        "Please log in to get your puzzle input",
+        "Please don't repeatedly request this endpoint before it unlocks",
+    ];
+
+    let start = input.trim_start().to_ascii_lowercase();
+    if start.is_empty() {
+        bail!("The server sent an empty input.");
+    }
+    if start.starts_with("<!doctype") || start.starts_with("<html") {
+        bail!("The server sent an HTML page instead of the input.");
+    }
+    if let Some(error) = ERRORS.iter().find(|error| input.contains(*error)) {
+        bail!("The server sent an error instead of the input: {error}.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_input, fetch, fetch_day, is_cached, FetchOptions, Outcome};
+    use crate::etc::{
+        client::{mock::Server, Client, Config},
+        temp::TempDir,
+    };
+    use std::fs;
+
+    #[test]
+    fn error_pages() {
+        assert!(check_input("467..114..\n...*......\n").is_ok());
+        assert!(check_input("\n").is_err());
+        assert!(check_input("<!DOCTYPE html>\n<html lang=\"en-us\">").is_err());
+        assert!(check_input("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.").is_err());
+    }
+
+    #[test]
+    fn fetch_from_mock() {
+        let server = Server::start(|request| match request.path.as_str() {
+            "/2023/day/1/input" => (200, "1abc2\npqr3stu8vwx\n".to_string()),
+            "/2023/day/2/input" => (200, "<!DOCTYPE html>\n<html></html>".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let client = Client::new(&Config {
+            session: Some("abc".to_string()),
+            base_url: server.base_url.clone(),
+            ..Config::default()
+        })
+        .unwrap();
+
+        let dir = TempDir::new("fetch");
+        fs::write(dir.join("day03.txt"), "cached").unwrap();
+        fs::write(dir.join("day04.txt"), "").unwrap();
+        fs::write(dir.join("day05.txt"), "<html>Error</html>").unwrap();
+
+        let outcome = fetch_day(&client, 1, &dir.join("day01.txt")).unwrap();
+        assert_eq!(outcome, Outcome::Fetched(18));
+        assert_eq!(
+            fs::read_to_string(dir.join("day01.txt")).unwrap(),
+            "1abc2\npqr3stu8vwx\n"
+        );
+        assert!(fetch_day(&client, 2, &dir.join("day02.txt")).is_err());
+        assert!(!dir.join("day02.txt").exists());
+        assert!(is_cached(&dir.join("day03.txt")));
+        assert!(!is_cached(&dir.join("day04.txt")));
+        assert!(!is_cached(&dir.join("day05.txt")));
+        let err = fetch_day(&client, 4, &dir.join("day04.txt")).unwrap_err();
+        assert!(format!("{err:#}").contains("status 404: Not Found"));
+        assert_eq!(fs::read_to_string(dir.join("day04.txt")).unwrap(), "");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|request| request.method == "GET"
+                && request.cookie.as_deref() == Some("session=abc")));
+    }
+
+    #[test]
+    fn fetch_missing_only() {
+        let server = Server::start(|request| match request.path.as_str() {
+            "/2023/day/2/input" => (200, "two\n".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let dir = TempDir::new("fetch-missing");
+        fs::write(dir.join("day01.txt"), "one\n").unwrap();
+        fs::write(dir.join("day02.txt"), "<!DOCTYPE html>\n<html></html>").unwrap();
+        let options = FetchOptions {
+            dir: dir.to_path_buf(),
+            ..FetchOptions::new(vec![1, 2])
+        };
+
+        // Cached inputs need no session.
+        let no_session = Config {
+            base_url: server.base_url.clone(),
+            ..Config::default()
+        };
+        let cached = FetchOptions {
+            days: vec![1],
+            ..options.clone()
+        };
+        assert!(fetch(&no_session, &cached).unwrap());
+        assert!(fetch(&no_session, &options).is_err());
+        assert!(server.requests().is_empty());
+
+        // The saved error page is replaced by the input.
+        let config = Config {
+            session: Some("abc".to_string()),
+            ..no_session
+        };
+        assert!(fetch(&config, &options).unwrap());
+        assert_eq!(fs::read_to_string(dir.join("day02.txt")).unwrap(), "two\n");
+        let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, ["/2023/day/2/input"]);
+    }
+}
@@ -0,0 +1,448 @@
+use super::{
+    bench::{Phase, Stats},
+    input::InputSource,
+    json::Json,
+    report::Report,
+    solver::Part,
+    Options,
+};
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Benchmark results of every run, one JSON object per day and phase.
+pub const PATH: &str = ".aoc/timings.jsonl";
+
+/// One line of the timing history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Identifies the run, milliseconds since the epoch when it was recorded.
+    pub run: String,
+    pub commit: String,
+    pub tag: Option<String>,
+    pub day: u8,
+    pub phase: String,
+    pub median_ms: f64,
+    pub setup: Setup,
+}
+
+/// The options a run was made with that affect its timings. Only runs with
+/// the same setup are compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setup {
+    /// The only part that was solved, both if `None`.
+    pub part: Option<Part>,
+    pub input: String,
+    pub jobs: usize,
+    /// Number of runs or time budget, see `Runs`.
+    pub bench: String,
+}
+
+impl Setup {
+    pub fn new(options: &Options) -> Self {
+        Setup {
+            part: options.part,
+            input: match &options.input {
+                InputSource::Default => "default".to_string(),
+                InputSource::File(path) | InputSource::Dir(path) => path.display().to_string(),
+                InputSource::Stdin => "stdin".to_string(),
+            },
+            jobs: options.jobs,
+            bench: options.bench.map_or(String::new(), |runs| runs.to_string()),
+        }
+    }
+
+    /// The settings that differ between `self` and `other`, e.g. `jobs 8 vs 1`.
+    pub fn differences(&self, other: &Setup) -> Vec<String> {
+        let part = |part: Option<Part>| part.map_or("both", part_name);
+        let mut differences = vec![];
+        if self.part != other.part {
+            differences.push(format!("part {} vs {}", part(self.part), part(other.part)));
+        }
+        if self.input != other.input {
+            differences.push(format!("input {} vs {}", self.input, other.input));
+        }
+        if self.jobs != other.jobs {
+            differences.push(format!("jobs {} vs {}", self.jobs, other.jobs));
+        }
+        if self.bench != other.bench {
+            differences.push(format!("bench {} vs {}", self.bench, other.bench));
+        }
+        differences
+    }
+}
+
+/// The part as given to `--part`.
+fn part_name(part: Part) -> &'static str {
+    match part {
+        Part::One => "1",
+        Part::Two => "2",
+    }
+}
+
+impl Entry {
+    fn to_json(&self, stats: &Stats) -> Json {
+        Json::object([
+            ("run", Json::from(self.run.as_str())),
+            ("commit", Json::from(self.commit.as_str())),
+            ("tag", Json::from(self.tag.as_deref())),
+            ("day", Json::from(self.day)),
+            ("phase", Json::from(self.phase.as_str())),
+            ("part", Json::from(self.setup.part.map(part_name))),
+            ("input", Json::from(self.setup.input.as_str())),
+            ("jobs", Json::from(self.setup.jobs)),
+            ("bench", Json::from(self.setup.bench.as_str())),
+            ("runs", Json::from(stats.runs)),
+            ("min_ms", Json::millis(stats.min)),
+            ("median_ms", Json::millis(stats.median)),
+            ("mean_ms", Json::millis(stats.mean)),
+            ("std_dev_ms", Json::millis(stats.std_dev)),
+            ("outliers", Json::from(stats.outliers)),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Result<Self> {
+        Ok(Entry {
+            run: json.str_field("run")?.to_string(),
+            commit: json.str_field("commit")?.to_string(),
+            tag: json.get("tag").and_then(Json::as_str).map(String::from),
+            day: json.num_field("day")? as u8,
+            phase: json.str_field("phase")?.to_string(),
+            median_ms: json.num_field("median_ms")?,
+            setup: Setup {
+                part: match json.get("part").and_then(Json::as_str) {
+                    Some(part) => Some(part.parse()?),
+                    None => None,
+                },
+                input: json.str_field("input")?.to_string(),
+                jobs: json.num_field("jobs")? as usize,
+                bench: json.str_field("bench")?.to_string(),
+            },
+        })
+    }
+}
+
+/// Appends the benchmark results in `reports`, made with `options`, to the history.
+pub fn append(reports: &[Report], options: &Options) -> Result<()> {
+    let run = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .to_string();
+    let commit = git_commit();
+    let setup = Setup::new(options);
+
+    let mut lines = String::new();
+    for report in reports {
+        let Some(bench) = &report.bench else { continue };
+        for (phase, stats) in &bench.phases {
+            let entry = Entry {
+                run: run.clone(),
+                commit: commit.clone(),
+                tag: options.tag.clone(),
+                day: report.day,
+                phase: phase.name().to_string(),
+                median_ms: stats.median,
+                setup: setup.clone(),
+            };
+            lines += &format!("{}\n", entry.to_json(stats));
+        }
+    }
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(dir) = Path::new(PATH).parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Could not create '{}'.", dir.display()))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(PATH)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .with_context(|| format!("Could not append to '{PATH}'."))
+}
+
+pub fn load() -> Result<Vec<Entry>> {
+    let history = fs::read_to_string(PATH).with_context(|| {
+        format!("Could not read the timing history '{PATH}', run with --bench first.")
+    })?;
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            Json::parse(line)
+                .and_then(|json| Entry::from_json(&json))
+                .with_context(|| format!("{PATH}:{}: invalid entry.", i + 1))
+        })
+        .collect()
+}
+
+/// `git describe` of the working tree, `unknown` outside of a git repository.
+fn git_commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Options of the `compare` subcommand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareOptions {
+    /// Tag or commit of the run to compare against, the previous run if `None`.
+    pub baseline: Option<String>,
+    /// Slowdown in percent above which a day counts as a regression.
+    pub threshold: f64,
+    pub phase: Phase,
+}
+
+/// A day's median in the baseline and the current run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub baseline_ms: Option<f64>,
+    pub current_ms: Option<f64>,
+}
+
+impl Change {
+    /// Relative change in percent, positive if the day got slower.
+    pub fn percent(&self) -> Option<f64> {
+        match (self.baseline_ms, self.current_ms) {
+            (Some(baseline), Some(current)) if baseline > 0.0 => {
+                Some(100.0 * (current - baseline) / baseline)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Compares the latest run in `entries` with the baseline run.
+///
+/// Without `--baseline`, the latest earlier run with the same setup is the
+/// baseline. A named baseline with a different setup is refused, its timings
+/// would show differences that have nothing to do with the code.
+///
+/// Returns the descriptions of both runs and the change per day.
+pub fn compare(
+    entries: &[Entry],
+    options: &CompareOptions,
+) -> Result<(String, String, Vec<Change>)> {
+    let mut runs: Vec<&Entry> = vec![];
+    for entry in entries {
+        if runs.last().is_none_or(|last| last.run != entry.run) {
+            runs.push(entry);
+        }
+    }
+
+    let Some((current, previous)) = runs.split_last() else {
+        bail!("The timing history is empty, run with --bench first.");
+    };
+    let baseline = match &options.baseline {
+        None if previous.is_empty() => {
+            bail!("There is only one run in the timing history, nothing to compare with.")
+        }
+        None => previous
+            .iter()
+            .rev()
+            .find(|run| run.setup == current.setup)
+            .context("No earlier run has the same --part, --input, --jobs and --bench options as the latest one.")?,
+        Some(name) => previous
+            .iter()
+            .rev()
+            .find(|run| run.tag.as_deref() == Some(name) || run.commit.starts_with(name.as_str()))
+            .with_context(|| format!("No earlier run is tagged '{name}' or at commit '{name}'."))?,
+    };
+    let differences = current.setup.differences(&baseline.setup);
+    if !differences.is_empty() {
+        bail!(
+            "The runs are not comparable, they differ in {}.",
+            differences.join(", ")
+        );
+    }
+
+    let median = |run: &str, day: u8| {
+        entries
+            .iter()
+            .find(|e| e.run == run && e.day == day && e.phase == options.phase.name())
+            .map(|e| e.median_ms)
+    };
+    let mut days: Vec<u8> = entries
+        .iter()
+        .filter(|e| e.run == current.run || e.run == baseline.run)
+        .map(|e| e.day)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let changes = days
+        .into_iter()
+        .map(|day| Change {
+            day,
+            baseline_ms: median(&baseline.run, day),
+            current_ms: median(&current.run, day),
+        })
+        .collect();
+    Ok((describe(current), describe(baseline), changes))
+}
+
+fn describe(run: &Entry) -> String {
+    match &run.tag {
+        Some(tag) => format!("'{tag}' ({})", run.commit),
+        None => run.commit.clone(),
+    }
+}
+
+/// Prints the comparison, returns whether any day regressed.
+pub fn print_compare(options: &CompareOptions) -> Result<bool> {
+    let (current, baseline, changes) = compare(&load()?, options)?;
+    println!(
+        "Comparing {current} against {baseline}, phase {}, threshold {:.1}%\n",
+        options.phase.name(),
+        options.threshold
+    );
+    println!("  Day      Baseline       Current    Change");
+
+    let mut regressed = false;
+    for change in &changes {
+        let ms = |ms: Option<f64>| ms.map_or("—".to_string(), |ms| format!("{ms:.4} ms"));
+        let (percent, flag) = match change.percent() {
+            Some(p) if p > options.threshold => {
+                regressed = true;
+                (format!("{p:+.1}%"), "  SLOWER")
+            }
+            Some(p) if p < -options.threshold => (format!("{p:+.1}%"), "  faster"),
+            Some(p) => (format!("{p:+.1}%"), ""),
+            None => ("—".to_string(), ""),
+        };
+        println!(
+            "   {:02} {:>13} {:>13} {:>9}{}",
+            change.day,
+            ms(change.baseline_ms),
+            ms(change.current_ms),
+            percent,
+            flag
+        );
+    }
+    Ok(regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, CompareOptions, Entry, Setup};
+    use crate::etc::{bench::Phase, json::Json, solver::Part};
+
+    fn setup(jobs: usize) -> Setup {
+        Setup {
+            part: None,
+            input: "default".to_string(),
+            jobs,
+            bench: "1s per day".to_string(),
+        }
+    }
+
+    fn entry(run: &str, tag: Option<&str>, day: u8, median_ms: f64) -> Entry {
+        Entry {
+            run: run.to_string(),
+            commit: format!("c{run}"),
+            tag: tag.map(String::from),
+            day,
+            phase: "total".to_string(),
+            median_ms,
+            setup: setup(1),
+        }
+    }
+
+    #[test]
+    fn compare_runs() {
+        let entries = vec![
+            entry("1", Some("fast"), 1, 1.0),
+            entry("2", None, 1, 4.0),
+            entry("2", None, 2, 4.0),
+            entry("3", None, 1, 2.0),
+            entry("3", None, 2, 2.0),
+        ];
+        let mut options = CompareOptions {
+            baseline: None,
+            threshold: 10.0,
+            phase: Phase::Total,
+        };
+
+        let (_, baseline, changes) = compare(&entries, &options).unwrap();
+        assert_eq!(baseline, "c2");
+        assert_eq!(changes[0].percent(), Some(-50.0));
+
+        options.baseline = Some("fast".to_string());
+        let (_, _, changes) = compare(&entries, &options).unwrap();
+        assert_eq!(changes[0].percent(), Some(100.0));
+        assert_eq!(changes[1].baseline_ms, None);
+
+        options.baseline = Some("nope".to_string());
+        assert!(compare(&entries, &options).is_err());
+        assert!(compare(
+            &entries[..1],
+            &CompareOptions {
+                baseline: None,
+                ..options
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn compare_same_setup_only() {
+        let mut parallel = entry("2", Some("parallel"), 1, 0.5);
+        parallel.setup = setup(8);
+        let mut part1 = entry("3", None, 1, 0.5);
+        part1.setup.part = Some(Part::One);
+        let entries = vec![
+            entry("1", None, 1, 2.0),
+            parallel,
+            part1,
+            entry("4", None, 1, 2.2),
+        ];
+        let mut options = CompareOptions {
+            baseline: None,
+            threshold: 10.0,
+            phase: Phase::Total,
+        };
+
+        // The runs in between were made with other options and are skipped.
+        let (_, baseline, changes) = compare(&entries, &options).unwrap();
+        assert_eq!(baseline, "c1");
+        assert!((changes[0].percent().unwrap() - 10.0).abs() < 1e-9);
+
+        options.baseline = Some("parallel".to_string());
+        let err = compare(&entries, &options).unwrap_err();
+        assert!(err.to_string().contains("jobs 1 vs 8"), "{err}");
+
+        options.baseline = None;
+        assert!(compare(&entries[..3], &options).is_err());
+    }
+
+    #[test]
+    fn setup_round_trip() {
+        let mut entry = entry("1", None, 1, 2.0);
+        entry.setup.part = Some(Part::Two);
+        let stats = crate::etc::bench::Stats {
+            runs: 10,
+            min: 1.0,
+            median: 2.0,
+            mean: 2.0,
+            std_dev: 0.1,
+            outliers: 0,
+        };
+        let json = Json::parse(&entry.to_json(&stats).to_string()).unwrap();
+        assert_eq!(Entry::from_json(&json).unwrap(), entry);
+    }
+}
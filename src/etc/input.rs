@@ -0,0 +1,82 @@
+use super::Error;
+use anyhow::{bail, Context, Result};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Directory the puzzle inputs are read from when nothing else is given.
+pub const DEFAULT_DIR: &str = "input";
+
+/// Where the puzzle inputs come from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// `input/dayNN.txt`, or the embedded input with the `embed-inputs` feature.
+    #[default]
+    Default,
+    /// A single file, only valid when one day is selected.
+    File(PathBuf),
+    /// A directory containing `dayNN.txt` files.
+    Dir(PathBuf),
+    /// Standard input, only valid when one day is selected.
+    Stdin,
+}
+
+impl InputSource {
+    /// Whether the source can only provide the input of a single day.
+    pub fn is_single(&self) -> bool {
+        matches!(self, InputSource::File(_) | InputSource::Stdin)
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("day{day:02}.txt")
+}
+
+/// Loads the input of `day` from `source`.
+pub fn load(day: u8, source: &InputSource) -> Result<String> {
+    match source {
+        InputSource::Default => load_default(day),
+        InputSource::File(path) => read_file(day, path),
+        InputSource::Dir(dir) => read_file(day, &dir.join(file_name(day))),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .with_context(|| format!("Could not read the input for day {day} from stdin."))?;
+            Ok(input)
+        }
+    }
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn load_default(day: u8) -> Result<String> {
+    read_file(day, &Path::new(DEFAULT_DIR).join(file_name(day)))
+}
+
+#[cfg(feature = "embed-inputs")]
+fn load_default(day: u8) -> Result<String> {
+    match EMBEDDED.get(day as usize - 1) {
+        Some(Some(input)) => Ok(input.to_string()),
+        _ => bail!(Error::InputMissing { day, path: None }),
+    }
+}
+
+fn read_file(day: u8, path: &Path) -> Result<String> {
+    if !path.is_file() {
+        bail!(Error::InputMissing {
+            day,
+            path: Some(path.to_path_buf()),
+        });
+    }
+    fs::read_to_string(path).with_context(|| {
+        format!(
+            "Could not read the input for day {day} from '{}'.",
+            path.display()
+        )
+    })
+}
+
+#[cfg(feature = "embed-inputs")]
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
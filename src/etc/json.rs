@@ -0,0 +1,347 @@
+use anyhow::{bail, Context};
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// A JSON value, just enough to write the runner's machine-readable output
+/// and read back its own files.
+///
+/// Numbers keep their textual representation, so `u128` answers survive unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>, I: IntoIterator<Item = (K, Json)>>(fields: I) -> Self {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// A duration in milliseconds, with nanosecond resolution.
+    pub fn millis(ms: f64) -> Self {
+        Json::Number(format!("{ms:.6}"))
+    }
+
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let mut parser = Parser { s, pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != s.len() {
+            bail!("Trailing characters at position {}.", parser.pos);
+        }
+        Ok(value)
+    }
+
+    /// The value of `key` if this is an object containing it.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The string `key` of an object, an error if it is missing or no string.
+    pub fn str_field(&self, key: &str) -> anyhow::Result<&str> {
+        self.get(key)
+            .and_then(Json::as_str)
+            .with_context(|| format!("Missing field '{key}'."))
+    }
+
+    /// The number `key` of an object, an error if it is missing or no number.
+    pub fn num_field(&self, key: &str) -> anyhow::Result<f64> {
+        self.get(key)
+            .and_then(Json::as_f64)
+            .with_context(|| format!("Missing field '{key}'."))
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.s[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, token: &str) -> anyhow::Result<()> {
+        self.skip_whitespace();
+        if !self.s[self.pos..].starts_with(token) {
+            bail!("Expected '{token}' at position {}.", self.pos);
+        }
+        self.pos += token.len();
+        Ok(())
+    }
+
+    fn value(&mut self) -> anyhow::Result<Json> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('n') => self.expect("null").map(|_| Json::Null),
+            Some('t') => self.expect("true").map(|_| Json::Bool(true)),
+            Some('f') => self.expect("false").map(|_| Json::Bool(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('[') => {
+                self.expect("[")?;
+                let mut values = vec![];
+                self.skip_whitespace();
+                if self.peek() == Some(']') {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        Some(']') => {
+                            self.pos += 1;
+                            return Ok(Json::Array(values));
+                        }
+                        _ => bail!("Expected ',' or ']' at position {}.", self.pos),
+                    }
+                }
+            }
+            Some('{') => {
+                self.expect("{")?;
+                let mut fields = vec![];
+                self.skip_whitespace();
+                if self.peek() == Some('}') {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    self.expect(":")?;
+                    fields.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        Some('}') => {
+                            self.pos += 1;
+                            return Ok(Json::Object(fields));
+                        }
+                        _ => bail!("Expected ',' or '}}' at position {}.", self.pos),
+                    }
+                }
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let len = self.s[self.pos..]
+                    .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                    .unwrap_or(self.s.len() - self.pos);
+                let number = &self.s[self.pos..self.pos + len];
+                number
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid number '{number}'."))?;
+                self.pos += len;
+                Ok(Json::Number(number.to_string()))
+            }
+            _ => bail!("Unexpected character at position {}.", self.pos),
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        self.expect("\"")?;
+        let mut string = String::new();
+        let mut chars = self.s[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(string);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('/') => string.push('/'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .with_context(|| format!("Invalid escape '\\u{hex}'."))?;
+                        string.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    _ => bail!("Invalid escape in string at position {}.", self.pos + i),
+                },
+                c => string.push(c),
+            }
+        }
+        bail!("Unterminated string at position {}.", self.pos)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+impl From<u8> for Json {
+    fn from(n: u8) -> Self {
+        Json::Number(n.to_string())
+    }
+}
+
+impl From<u64> for Json {
+    fn from(n: u64) -> Self {
+        Json::Number(n.to_string())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(n: usize) -> Self {
+        Json::Number(n.to_string())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => b.fmt(f),
+            Json::Number(n) => f.write_str(n),
+            Json::String(s) => write_string(f, s),
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    value.fmt(f)?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    f.write_char(':')?;
+                    value.fmt(f)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, s: &str) -> Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Json;
+
+    #[test]
+    fn parse() {
+        let json =
+            Json::parse(r#" {"a": [1, -2.5e3, null], "b": {"c": "x\"\u00e9\n"}, "d": true} "#)
+                .unwrap();
+        assert_eq!(
+            json.get("a").unwrap(),
+            &Json::Array(vec![
+                Json::Number("1".to_string()),
+                Json::Number("-2.5e3".to_string()),
+                Json::Null,
+            ])
+        );
+        assert_eq!(
+            json.get("b").unwrap().get("c").unwrap().as_str(),
+            Some("x\"é\n")
+        );
+        assert_eq!(json.get("d"), Some(&Json::Bool(true)));
+        assert_eq!(json.get("b").unwrap().str_field("c").unwrap(), "x\"é\n");
+        assert!(json.str_field("d").is_err());
+        assert!(json.num_field("e").is_err());
+
+        assert!(Json::parse("{\"a\": }").is_err());
+        assert!(Json::parse("[1, 2").is_err());
+        assert!(Json::parse("\"open").is_err());
+        assert!(Json::parse("1 2").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = Json::object([
+            ("s", Json::from("tab\tquote\"")),
+            ("n", Json::millis(1.5)),
+            ("a", Json::Array(vec![])),
+            ("o", Json::Object(vec![])),
+        ]);
+        assert_eq!(Json::parse(&json.to_string()).unwrap(), json);
+    }
+
+    #[test]
+    fn display() {
+        let json = Json::object([
+            ("day", Json::from(3u8)),
+            ("answer", Json::Number(u128::MAX.to_string())),
+            ("error", Json::from("line 1\n\"quoted\"\u{1}")),
+            ("list", Json::Array(vec![Json::Null, Json::from(true)])),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r#"{"day":3,"answer":340282366920938463463374607431768211455,"error":"line 1\n\"quoted\"\u0001","list":[null,true]}"#
+        );
+    }
+}
@@ -1,4 +1,30 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod bigint;
+pub mod cancel;
+pub mod cli;
+pub mod client;
+pub mod error;
+pub mod examples;
+pub mod extract;
+pub mod fetch;
+pub mod history;
+pub mod input;
+pub mod json;
+pub mod report;
+pub mod runner;
+pub mod selection;
 pub mod solution;
+pub mod solver;
+pub mod submit;
+pub mod table;
+#[cfg(test)]
+pub mod temp;
 
-pub use solution::ErasedError;
-pub use solution::Solution;
+pub use cancel::Cancel;
+pub use cli::{Command, Options};
+pub use error::Error;
+pub use selection::parse_selection;
+pub use solution::{Answer, Answers, Solution};
+pub use solver::Solver;
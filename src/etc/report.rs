@@ -0,0 +1,511 @@
+use super::{
+    alloc::{format_bytes, AllocStats},
+    answers::Verdict,
+    bench::BenchStats,
+    json::Json,
+    solver::millis,
+    solver::{Part, Timings},
+    table::{self, Colors},
+    Answer, Answers, Error, Options, Solution,
+};
+use crate::days::Day;
+use anyhow::bail;
+use std::{str::FromStr, time::Duration};
+
+/// How the results of a run are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("Unknown format '{s}', expected one of text, json or csv."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Error(Error),
+    Panic(String),
+    Timeout(Duration),
+    NotImplemented,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error(_) => "error",
+            Status::Panic(_) => "panic",
+            Status::Timeout(_) => "timeout",
+            Status::NotImplemented => "not implemented",
+        }
+    }
+
+    /// Why the day failed, if it did.
+    pub fn error(&self) -> Option<String> {
+        match self {
+            Status::Error(err) => Some(err.to_string()),
+            Status::Panic(msg) => Some(msg.clone()),
+            Status::Timeout(timeout) => {
+                Some(format!("TIMEOUT after {:.3} s", timeout.as_secs_f64()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Everything that is known about one day after running it.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    /// Name of the example the day was run on, `None` for the puzzle input.
+    pub example: Option<String>,
+    pub status: Status,
+    pub answers: Option<Answers>,
+    /// Comparison of the answers with the known-good ones.
+    pub verdicts: [Option<Verdict>; 2],
+    pub timings: Option<Timings>,
+    /// Allocations of the (first) run.
+    pub allocs: Option<AllocStats>,
+    pub bench: Option<BenchStats>,
+}
+
+impl Report {
+    pub fn new(day: &Day) -> Self {
+        Report {
+            day: day.number,
+            title: day.title,
+            example: None,
+            status: Status::Ok,
+            answers: None,
+            verdicts: [None, None],
+            timings: None,
+            allocs: None,
+            bench: None,
+        }
+    }
+
+    /// Runtime the day contributes to the total, the median when benchmarking.
+    pub fn runtime_ms(&self) -> f64 {
+        match (&self.bench, &self.timings) {
+            (Some(stats), _) => stats.total().median,
+            (None, Some(timings)) => millis(timings.total()),
+            (None, None) => 0.0,
+        }
+    }
+
+    /// The day number, followed by the example if the day ran on one.
+    pub fn label(&self) -> String {
+        match &self.example {
+            Some(example) => format!("{} ({example})", self.day),
+            None => self.day.to_string(),
+        }
+    }
+
+    /// Whether any answer differs from the known-good one.
+    pub fn is_wrong(&self) -> bool {
+        self.verdicts
+            .iter()
+            .flatten()
+            .any(|verdict| matches!(verdict, Verdict::Wrong { .. }))
+    }
+
+    pub fn verdict(&self, part: Part) -> Option<&Verdict> {
+        self.verdicts[part.index()].as_ref()
+    }
+
+    pub fn answer(&self, part: Part) -> &Answer {
+        static NOT_RUN: Answer = Answer::NotRun;
+        self.answers
+            .as_ref()
+            .map_or(&NOT_RUN, |answers| &answers[part])
+    }
+}
+
+/// Lists sorted `days` with consecutive ones as ranges, e.g. `day 5` or `days 5, 7-25`.
+fn format_days(days: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = vec![];
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+    let list: Vec<String> = ranges
+        .iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{start}-{end}"),
+        })
+        .collect();
+    match days {
+        [_] => format!("day {}", list[0]),
+        _ => format!("days {}", list.join(", ")),
+    }
+}
+
+/// Prints a single day in human readable form.
+pub fn print_text(report: &Report) {
+    if report.status == Status::NotImplemented {
+        return;
+    }
+
+    match &report.example {
+        Some(example) => println!(
+            "\n=== Day {:02}: {} (example {example}) ===",
+            report.day, report.title
+        ),
+        None => println!("\n=== Day {:02}: {} ===", report.day, report.title),
+    }
+    for part in Part::ALL {
+        match report.answer(part) {
+            Answer::NotRun => {}
+            Answer::Solved(solution) if solution.is_multiline() => {
+                let verdict = report.verdict(part);
+                let name = verdict.map_or(String::new(), |v| format!(" ({})", v.name()));
+                println!("  · Part {}:{}", part, name);
+                println!("      {}", solution.to_string().replace('\n', "\n      "));
+                if let Some(Verdict::Wrong { expected }) = verdict {
+                    println!("    expected:");
+                    println!("      {}", expected.replace('\n', "\n      "));
+                }
+            }
+            answer => {
+                let verdict = verdict_suffix(report.verdict(part));
+                println!("  · Part {}: {}{}", part, answer, verdict);
+            }
+        }
+    }
+    match &report.status {
+        Status::Error(err) => println!("  · Error: {}", indent(&err.to_string())),
+        Status::Panic(msg) => println!("  · Panicked: {}", msg),
+        Status::Timeout(timeout) => println!("  · TIMEOUT after {:.3} s", timeout.as_secs_f64()),
+        _ => {}
+    }
+
+    match (&report.bench, &report.timings) {
+        (Some(stats), _) => {
+            let stats = stats.total();
+            println!("  · Runs: {}", stats.runs);
+            println!(
+                "  · Elapsed: {:.4} ms median, {:.4} ms min, {:.4} ms ± {:.4} ms mean",
+                stats.median, stats.min, stats.mean, stats.std_dev
+            );
+            println!(
+                "  · Outliers: {} ({:.1}%)",
+                stats.outliers,
+                100.0 * stats.outliers as f64 / stats.runs as f64
+            );
+        }
+        (None, Some(timings)) => {
+            let mut phases = format!("parse {:.4} ms", millis(timings.parse));
+            for (name, elapsed) in [("part 1", timings.part1), ("part 2", timings.part2)] {
+                if let Some(elapsed) = elapsed {
+                    phases += &format!(", {} {:.4} ms", name, millis(elapsed));
+                }
+            }
+            println!(
+                "  · Elapsed: {:.4} ms ({})",
+                millis(timings.total()),
+                phases
+            );
+        }
+        (None, None) => {}
+    }
+
+    if let Some(allocs) = &report.allocs {
+        println!(
+            "  · Allocations: {} ({} total, {} peak)",
+            allocs.count,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak_bytes)
+        );
+    }
+}
+
+/// Indents the continuation lines of a multi-line message to fit a `  · ` list.
+fn indent(message: &str) -> String {
+    message.replace('\n', "\n    ")
+}
+
+fn verdict_suffix(verdict: Option<&Verdict>) -> String {
+    match verdict {
+        None => String::new(),
+        Some(Verdict::Wrong { expected }) if expected.contains('\n') => {
+            format!(" (wrong, expected{})", expected.replace('\n', "\n      "))
+        }
+        Some(Verdict::Wrong { expected }) => format!(" (wrong, expected {expected})"),
+        Some(verdict) => format!(" ({})", verdict.name()),
+    }
+}
+
+/// Prints what is left after all days were printed with `print_text`.
+pub fn print_text_summary(reports: &[Report], options: &Options, wall_ms: f64) {
+    if reports
+        .iter()
+        .any(|report| report.status != Status::NotImplemented)
+    {
+        print!(
+            "\n{}",
+            table::render(reports, options.sort, Colors::detect())
+        );
+    }
+
+    let skipped: Vec<u8> = reports
+        .iter()
+        .filter(|report| report.status == Status::NotImplemented)
+        .map(|report| report.day)
+        .collect();
+    if !skipped.is_empty() {
+        println!("\nNot implemented: {}", format_days(&skipped));
+    }
+
+    let wrong: Vec<String> = reports
+        .iter()
+        .filter(|report| report.is_wrong())
+        .map(Report::label)
+        .collect();
+    if !wrong.is_empty() {
+        println!("\nWrong answers: days {}", wrong.join(", "));
+    }
+
+    let failed: Vec<&Report> = reports
+        .iter()
+        .filter(|report| report.status.error().is_some())
+        .collect();
+    if !failed.is_empty() {
+        println!("\nFailed days:");
+        for report in failed {
+            let example = match &report.example {
+                Some(example) => format!(", example {example}"),
+                None => String::new(),
+            };
+            println!(
+                "  · Day {:02}{example} ({}): {}",
+                report.day,
+                report.status.name(),
+                indent(&report.status.error().unwrap_or_default())
+            );
+        }
+    }
+
+    let runtime: f64 = reports.iter().map(Report::runtime_ms).sum();
+    match options.bench.is_some() {
+        false => println!("Total runtime: {:.4} ms", runtime),
+        true => println!("Total runtime: {:.4} ms (sum of medians)", runtime),
+    }
+    if options.jobs > 1 {
+        println!("Wall-clock time: {:.4} ms ({} jobs)", wall_ms, options.jobs);
+    }
+}
+
+pub fn print_json(reports: &[Report], wall_ms: f64) {
+    let runtime: f64 = reports.iter().map(Report::runtime_ms).sum();
+    let json = Json::object([
+        (
+            "days",
+            Json::Array(reports.iter().map(report_to_json).collect()),
+        ),
+        ("total_ms", Json::millis(runtime)),
+        ("wall_ms", Json::millis(wall_ms)),
+    ]);
+    println!("{json}");
+}
+
+fn report_to_json(report: &Report) -> Json {
+    let [p1, p2] = Part::ALL.map(|part| match report.answer(part) {
+        Answer::Solved(solution) => solution_to_json(solution, report.verdict(part)),
+        Answer::Unsolved => Json::object([
+            ("type", Json::Null),
+            ("value", Json::Null),
+            ("verdict", Json::from("unsolved")),
+            ("expected", Json::Null),
+        ]),
+        Answer::NotRun => Json::Null,
+    });
+    let timings = report.timings.map_or(Json::Null, |timings| {
+        Json::object([
+            ("parse", Json::millis(millis(timings.parse))),
+            (
+                "part1",
+                Json::from(timings.part1.map(|d| Json::millis(millis(d)))),
+            ),
+            (
+                "part2",
+                Json::from(timings.part2.map(|d| Json::millis(millis(d)))),
+            ),
+            ("total", Json::millis(millis(timings.total()))),
+        ])
+    });
+    let bench = report.bench.as_ref().map_or(Json::Null, |stats| {
+        let stats = stats.total();
+        Json::object([
+            ("runs", Json::from(stats.runs)),
+            ("min", Json::millis(stats.min)),
+            ("median", Json::millis(stats.median)),
+            ("mean", Json::millis(stats.mean)),
+            ("std_dev", Json::millis(stats.std_dev)),
+            ("outliers", Json::from(stats.outliers)),
+        ])
+    });
+
+    let allocs = report.allocs.map_or(Json::Null, |allocs| {
+        Json::object([
+            ("count", Json::Number(allocs.count.to_string())),
+            ("bytes", Json::Number(allocs.bytes.to_string())),
+            ("peak_bytes", Json::Number(allocs.peak_bytes.to_string())),
+        ])
+    });
+
+    Json::object([
+        ("day", Json::from(report.day)),
+        ("example", Json::from(report.example.as_deref())),
+        ("title", Json::from(report.title)),
+        ("status", Json::from(report.status.name())),
+        ("part1", p1),
+        ("part2", p2),
+        ("timings_ms", timings),
+        ("bench_ms", bench),
+        ("allocs", allocs),
+        ("error", Json::from(report.status.error())),
+    ])
+}
+
+fn solution_to_json(solution: &Solution, verdict: Option<&Verdict>) -> Json {
+    let value = match solution {
+        Solution::Bool(b) => Json::from(*b),
+        Solution::Str(_) | Solution::Char(_) | Solution::Grid(_) => {
+            Json::from(solution.to_string())
+        }
+        Solution::F32(x) if !x.is_finite() => Json::from(solution.to_string()),
+        Solution::F64(x) if !x.is_finite() => Json::from(solution.to_string()),
+        number => Json::Number(number.to_string()),
+    };
+    let expected = match verdict {
+        Some(Verdict::Wrong { expected }) => Json::from(expected.as_str()),
+        _ => Json::Null,
+    };
+    Json::object([
+        ("type", Json::from(solution.variant())),
+        ("value", value),
+        ("verdict", Json::from(verdict.map(Verdict::name))),
+        ("expected", expected),
+    ])
+}
+
+const CSV_HEADER: &str = "day,title,example,status,part1_type,part1,part1_verdict,\
+part2_type,part2,part2_verdict,\
+parse_ms,part1_ms,part2_ms,total_ms,bench_runs,bench_min_ms,bench_median_ms,\
+bench_mean_ms,bench_std_dev_ms,bench_outliers,alloc_count,alloc_bytes,alloc_peak_bytes,error";
+
+pub fn print_csv(reports: &[Report]) {
+    println!("{CSV_HEADER}");
+    for report in reports {
+        let mut row: Vec<String> = vec![
+            report.day.to_string(),
+            report.title.to_string(),
+            report.example.clone().unwrap_or_default(),
+            report.status.name().to_string(),
+        ];
+        for part in Part::ALL {
+            match report.answer(part) {
+                Answer::Solved(solution) => {
+                    row.push(solution.variant().to_string());
+                    row.push(solution.to_string());
+                    row.push(report.verdict(part).map_or("", Verdict::name).to_string());
+                }
+                Answer::Unsolved => row.extend(["", "", "unsolved"].map(String::from)),
+                Answer::NotRun => row.extend([""; 3].map(String::from)),
+            }
+        }
+        match &report.timings {
+            Some(t) => row.extend(
+                [Some(t.parse), t.part1, t.part2, Some(t.total())]
+                    .map(|d| d.map_or(String::new(), |d| format!("{:.6}", millis(d)))),
+            ),
+            None => row.extend([""; 4].map(String::from)),
+        }
+        match &report.bench {
+            Some(s) => {
+                let s = s.total();
+                row.push(s.runs.to_string());
+                row.extend([s.min, s.median, s.mean, s.std_dev].map(|ms| format!("{ms:.6}")));
+                row.push(s.outliers.to_string());
+            }
+            None => row.extend([""; 6].map(String::from)),
+        }
+        match &report.allocs {
+            Some(a) => row.extend([a.count, a.bytes, a.peak_bytes].map(|n| n.to_string())),
+            None => row.extend([""; 3].map(String::from)),
+        }
+        row.push(report.status.error().unwrap_or_default().to_string());
+
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        println!("{}", row.join(","));
+    }
+}
+
+/// Quotes a CSV field if needed, following RFC 4180.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, format_days, solution_to_json, Format};
+    use crate::etc::{answers::Verdict, json::Json, Solution};
+
+    #[test]
+    fn format_from_str() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn day_lists() {
+        assert_eq!(format_days(&[5]), "day 5");
+        assert_eq!(format_days(&[5, 6]), "days 5-6");
+        assert_eq!(format_days(&[2, 5, 6, 7, 25]), "days 2, 5-7, 25");
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("Trebuchet?!"), "Trebuchet?!");
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+
+    #[test]
+    fn grid_json() {
+        let grid = Solution::from(vec![".##.".to_string(), "#..#".to_string()]);
+        let text = solution_to_json(&grid, Some(&Verdict::Correct)).to_string();
+        assert_eq!(
+            text,
+            r#"{"type":"Grid","value":".##.\n#..#","verdict":"correct","expected":null}"#
+        );
+
+        let parsed = Json::parse(&text).unwrap();
+        let value = parsed
+            .get("value")
+            .and_then(|value| value.as_str())
+            .unwrap();
+        assert_eq!(value.parse::<Solution>().unwrap(), grid);
+    }
+}
@@ -0,0 +1,395 @@
+use super::{
+    alloc,
+    answers::Known,
+    bench,
+    cancel::Cancel,
+    examples::{self, Example},
+    input::{self, InputSource},
+    report::{Report, Status},
+    Error, Options,
+};
+use crate::days::Day;
+use anyhow::{bail, Result};
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Once,
+    },
+    thread,
+};
+
+/// A day to run, on its puzzle input or on one of its examples.
+#[derive(Clone)]
+pub struct Task {
+    pub day: &'static Day,
+    pub example: Option<Example>,
+}
+
+impl Task {
+    fn report(&self) -> Report {
+        let mut report = Report::new(self.day);
+        report.example = self.example.as_ref().map(|example| example.name.clone());
+        report
+    }
+}
+
+/// The tasks for `days`, one per example of each day with `--examples`.
+///
+/// Implemented days without examples are skipped with a warning, stubs are
+/// kept so they are reported as not implemented.
+pub fn tasks(days: &[&'static Day], options: &Options) -> Result<Vec<Task>> {
+    let mut tasks = vec![];
+    for &day in days {
+        if !options.examples {
+            tasks.push(Task { day, example: None });
+            continue;
+        }
+        let found = examples::find(day.number, Path::new(examples::DIR))?;
+        if found.is_empty() && !day.implemented {
+            tasks.push(Task { day, example: None });
+        } else if found.is_empty() {
+            eprintln!(
+                "Warning: day {} has no examples in {}/.",
+                day.number,
+                examples::DIR
+            );
+        }
+        tasks.extend(found.into_iter().map(|example| Task {
+            day,
+            example: Some(example),
+        }));
+    }
+    Ok(tasks)
+}
+
+/// Runs `tasks` on `options.jobs` threads.
+///
+/// `on_report` is called in order as soon as a task and all tasks before
+/// it are done, the reports are returned in the same order.
+pub fn run_days<F: FnMut(&Report)>(
+    days: &[Task],
+    options: &Options,
+    mut on_report: F,
+) -> Vec<Report> {
+    install_panic_hook();
+    if options.jobs <= 1 {
+        return days
+            .iter()
+            .map(|day| {
+                let report = run_day(day, options);
+                on_report(&report);
+                report
+            })
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut done: Vec<Option<Report>> = days.iter().map(|_| None).collect();
+    let mut reports = Vec::with_capacity(days.len());
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(i) else { break };
+                if sender.send((i, run_day(day, options))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        for (i, report) in receiver {
+            done[i] = Some(report);
+            while let Some(report) = done.get_mut(reports.len()).and_then(Option::take) {
+                on_report(&report);
+                reports.push(report);
+            }
+        }
+    });
+
+    reports
+}
+
+/// Runs a single day, or one of its examples, as configured by `options`.
+///
+/// Errors and panics are recorded in the report instead of being
+/// propagated, so one failing day does not stop the others.
+///
+/// With a timeout the day runs on its own thread. When the timeout expires,
+/// the day is cancelled and reported as timed out. A solver that never
+/// checks its `Cancel` token keeps running in the background until the
+/// process exits.
+pub fn run_day(task: &Task, options: &Options) -> Report {
+    let day = task.day;
+    let Some(timeout) = options.timeout else {
+        return run_isolated(task, options, &Cancel::new());
+    };
+
+    let cancel = Cancel::new();
+    let (sender, receiver) = mpsc::channel();
+    let spawned = {
+        let (task, options, cancel) = (task.clone(), options.clone(), cancel.clone());
+        thread::Builder::new()
+            .name(format!("day{:02}", day.number))
+            .spawn(move || {
+                let _ = sender.send(run_isolated(&task, &options, &cancel));
+            })
+    };
+    if let Err(err) = spawned {
+        let mut report = task.report();
+        report.status = Status::Error(Error::Internal(format!("Could not spawn a thread: {err}")));
+        return report;
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(report) => report,
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            let mut report = task.report();
+            report.status = Status::Timeout(timeout);
+            report
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let mut report = task.report();
+            report.status = Status::Error(Error::Internal(
+                "The day stopped without a report.".to_string(),
+            ));
+            report
+        }
+    }
+}
+
+fn run_isolated(task: &Task, options: &Options, cancel: &Cancel) -> Report {
+    let mut report = task.report();
+    match panic::catch_unwind(AssertUnwindSafe(|| {
+        solve(task, options, cancel, &mut report)
+    })) {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => {
+            report.status = match Error::from_anyhow(&err) {
+                Error::Timeout => Status::Timeout(options.timeout.unwrap_or_default()),
+                error => Status::Error(error),
+            }
+        }
+        Err(payload) => report.status = Status::Panic(panic_message(payload.as_ref())),
+    }
+    report
+}
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Remembers where a panic happened, so it can be shown in the summary.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(|l| l.to_string());
+            PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            default_hook(info);
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let msg = match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(msg), _) => msg.to_string(),
+        (_, Some(msg)) => msg.clone(),
+        _ => "Box<dyn Any>".to_string(),
+    };
+    match PANIC_LOCATION.with(|cell| cell.borrow_mut().take()) {
+        Some(location) => format!("{msg} at {location}"),
+        None => msg,
+    }
+}
+
+fn solve(task: &Task, options: &Options, cancel: &Cancel, report: &mut Report) -> Result<()> {
+    let day = task.day;
+    let (input, known, part) = match &task.example {
+        None => {
+            let input = match input::load(day.number, &options.input) {
+                Err(err)
+                    if matches!(Error::from_anyhow(&err), Error::InputMissing { .. })
+                        && !day.implemented =>
+                {
+                    report.status = Status::NotImplemented;
+                    return Ok(());
+                }
+                input => input?,
+            };
+            (input, Known::load(day.number)?, options.part)
+        }
+        Some(example) => {
+            let known = example.expected()?;
+            let part = options.part.or(examples::only_part(&known));
+            let source = InputSource::File(example.input.clone());
+            (input::load(day.number, &source)?, known, part)
+        }
+    };
+
+    let (result, allocs) = alloc::measure(|| (day.run)(&input, part, cancel));
+    let (answers, timings) = result.map_err(|err| match Error::from_anyhow(&err) {
+        error @ Error::Parse { .. } => error.with_input(day.number, &input).into(),
+        _ => err,
+    })?;
+    if answers.is_not_run() {
+        bail!("Nothing was run, day {} has no part 2.", day.number);
+    }
+    if answers.is_unimplemented() {
+        report.status = Status::NotImplemented;
+        return Ok(());
+    }
+    report.allocs = Some(allocs);
+    report.verdicts = known.verify(&answers);
+    if options.record {
+        match &task.example {
+            Some(example) => Known::record_file(&example.answers, &answers)?,
+            None => Known::record(day.number, &answers)?,
+        }
+    }
+    report.answers = Some(answers);
+    report.timings = Some(timings);
+
+    if let Some(runs) = options.bench {
+        report.bench = Some(bench::bench(runs, || {
+            cancel.check()?;
+            Ok((day.run)(&input, part, cancel)?.1)
+        })?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_day, Cancel, Options, Status, Task};
+    use crate::{
+        days::Day,
+        etc::{
+            solver::{self, Part, Timings},
+            Answer, Answers,
+        },
+    };
+    use anyhow::Result;
+
+    fn options(args: &[&str]) -> Options {
+        let args = ["1", "--input", "input/test/day01.txt"].iter().chain(args);
+        Options::parse(args.map(|arg| arg.to_string()))
+            .unwrap()
+            .unwrap()
+    }
+
+    fn panics(_: &str, _: Option<Part>, _: &Cancel) -> Result<(Answers, Timings)> {
+        panic!("parser bug")
+    }
+
+    fn stub(_: &str, part: Option<Part>, _: &Cancel) -> Result<(Answers, Timings)> {
+        let mut answers = Answers::default();
+        for p in Part::ALL
+            .into_iter()
+            .filter(|&p| part.is_none_or(|part| part == p))
+        {
+            answers[p] = Answer::Unsolved;
+        }
+        Ok((answers, Timings::default()))
+    }
+
+    fn spins(_: &str, _: Option<Part>, cancel: &Cancel) -> Result<(Answers, Timings)> {
+        loop {
+            cancel.check()?;
+        }
+    }
+
+    #[test]
+    fn panic_is_isolated() {
+        static DAY: Day = Day {
+            number: 1,
+            title: "Panics",
+            run: panics,
+            implemented: true,
+            has_part2: true,
+        };
+        let task = Task {
+            day: &DAY,
+            example: None,
+        };
+        let report = run_day(&task, &options(&[]));
+        match report.status {
+            Status::Panic(msg) => assert!(msg.starts_with("parser bug")),
+            status => panic!("unexpected status {status:?}"),
+        }
+    }
+
+    #[test]
+    fn stub_is_not_implemented() {
+        static DAY: Day = Day {
+            number: 1,
+            title: "Stub",
+            run: stub,
+            implemented: false,
+            has_part2: true,
+        };
+        let task = Task {
+            day: &DAY,
+            example: None,
+        };
+        let report = run_day(&task, &options(&[]));
+        assert_eq!(report.status, Status::NotImplemented);
+
+        assert!(crate::days::get(1).unwrap().implemented);
+        assert!(!crate::days::get(25).unwrap().implemented);
+    }
+
+    #[test]
+    fn nothing_to_run() {
+        static DAY: Day = Day {
+            number: 25,
+            title: "Single part",
+            run: solver::run::<crate::days::day25::Day25>,
+            implemented: true,
+            has_part2: false,
+        };
+        let task = Task {
+            day: &DAY,
+            example: None,
+        };
+        let report = run_day(&task, &options(&["--part", "2"]));
+        assert!(
+            matches!(report.status, Status::Error(_)),
+            "{:?}",
+            report.status
+        );
+        assert!(report.answers.is_none() && report.timings.is_none());
+        assert!(!crate::days::get(25).unwrap().has(Part::Two));
+    }
+
+    #[test]
+    fn timeout_cancels() {
+        static DAY: Day = Day {
+            number: 1,
+            title: "Spins",
+            run: spins,
+            implemented: true,
+            has_part2: true,
+        };
+        let task = Task {
+            day: &DAY,
+            example: None,
+        };
+        let report = run_day(&task, &options(&["--timeout", "0.05"]));
+        assert!(matches!(report.status, Status::Timeout(_)));
+    }
+}
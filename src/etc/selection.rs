@@ -0,0 +1,114 @@
+use crate::days::DAYS;
+use anyhow::{anyhow, bail, Result};
+use std::collections::BTreeSet;
+
+/// Parses the day selection given on the command line.
+///
+/// Every argument is a comma separated list of terms:
+/// - `all`: every day of the calendar,
+/// - `latest`: the last implemented day,
+/// - `7` or `1-9`: a single day or an inclusive range,
+/// - `!12` or `!20-25`: remove days from the selection.
+///
+/// Exclusions are applied after all inclusions. A selection made up of
+/// exclusions only starts from `all`.
+pub fn parse_selection<S: AsRef<str>>(args: &[S]) -> Result<Vec<u8>> {
+    let mut included = BTreeSet::new();
+    let mut excluded = BTreeSet::new();
+
+    for arg in args {
+        for term in arg.as_ref().split(',').map(str::trim) {
+            if term.is_empty() {
+                bail!("Empty day selection in '{}'.", arg.as_ref());
+            }
+            match term.strip_prefix('!') {
+                Some(term) => excluded.extend(parse_term(term)?),
+                None => included.extend(parse_term(term)?),
+            }
+        }
+    }
+
+    if included.is_empty() && !excluded.is_empty() {
+        included.extend(DAYS.iter().map(|day| day.number));
+    }
+
+    let days: Vec<u8> = included.difference(&excluded).copied().collect();
+    if days.is_empty() {
+        bail!("The selection does not contain any day.");
+    }
+    Ok(days)
+}
+
+fn parse_term(term: &str) -> Result<Vec<u8>> {
+    match term {
+        "all" => Ok(DAYS.iter().map(|day| day.number).collect()),
+        "latest" => DAYS
+            .iter()
+            .rev()
+            .find(|day| day.implemented)
+            .map(|day| vec![day.number])
+            .ok_or_else(|| anyhow!("No day is implemented yet.")),
+        _ => match term.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    bail!("Invalid range '{term}': {start} is greater than {end}.");
+                }
+                Ok((start..=end).collect())
+            }
+            None => Ok(vec![parse_day(term)?]),
+        },
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8> {
+    let day: u8 = s.trim().parse().map_err(|_| {
+        anyhow!("Not a valid day: '{s}'. Expected a number, a range like '1-9', 'all' or 'latest'.")
+    })?;
+    if !DAYS.iter().any(|d| d.number == day) {
+        bail!(
+            "Not a valid day: {day}. Days range from 1 to {}.",
+            DAYS.len()
+        );
+    }
+    Ok(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_selection;
+
+    #[test]
+    fn lists_and_ranges() {
+        assert_eq!(parse_selection(&["1,3,7"]).unwrap(), vec![1, 3, 7]);
+        assert_eq!(parse_selection(&["3-5", "1"]).unwrap(), vec![1, 3, 4, 5]);
+        assert_eq!(parse_selection(&["all"]).unwrap().len(), 25);
+    }
+
+    #[test]
+    fn exclusions() {
+        assert_eq!(parse_selection(&["1-5", "!2,!4"]).unwrap(), vec![1, 3, 5]);
+        assert_eq!(parse_selection(&["!2-25"]).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn latest() {
+        let latest = parse_selection(&["latest"]).unwrap();
+        assert_eq!(latest.len(), 1);
+        assert!(crate::days::get(latest[0]).unwrap().implemented);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_selection(&["0"]).is_err());
+        assert!(parse_selection(&["26"]).is_err());
+        assert!(parse_selection(&["5-3"]).is_err());
+        let err = parse_selection(&["one"]).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "Not a valid day: 'one'. Expected a number, a range like '1-9', 'all' or 'latest'."
+        );
+        assert!(parse_selection(&["1,,2"]).is_err());
+        assert!(parse_selection(&["!1-25"]).is_err());
+    }
+}
@@ -1,34 +1,416 @@
-#![allow(unused)]
+use super::{
+    bigint::{BigInt, BigUint},
+    solver::Part,
+};
 use std::{
-    error::Error,
-    fmt::{Display, Formatter, Result},
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 use Solution::*;
 
-pub type ErasedError = Box<dyn Error + Send + Sync + 'static>;
+/// Declares the `Solution` variants together with a `From` impl for each type.
+///
+/// Integers and floats are grouped, so that `numeric` can compare them across
+/// variants.
+macro_rules! solution {
+    (
+        signed: $($signed:ident($signed_ty:ty)),*;
+        unsigned: $($unsigned:ident($unsigned_ty:ty)),*;
+        float: $($float:ident($float_ty:ty)),*;
+        other: $($other:ident($other_ty:ty)),*;
+    ) => {
+        /// The answer of a part, convert any primitive or string with `.into()`.
+        #[derive(Debug, Clone)]
+        pub enum Solution {
+            $($signed($signed_ty),)*
+            $($unsigned($unsigned_ty),)*
+            $($float($float_ty),)*
+            $($other($other_ty),)*
+        }
+
+        impl Display for Solution {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                match self {
+                    $($signed(x) => x.fmt(f),)*
+                    $($unsigned(x) => x.fmt(f),)*
+                    $($float(x) => x.fmt(f),)*
+                    $($other(x) => x.fmt(f),)*
+                }
+            }
+        }
+
+        impl Solution {
+            /// Name of the variant, e.g. `"U64"`.
+            pub fn variant(&self) -> &'static str {
+                match self {
+                    $($signed(_) => stringify!($signed),)*
+                    $($unsigned(_) => stringify!($unsigned),)*
+                    $($float(_) => stringify!($float),)*
+                    $($other(_) => stringify!($other),)*
+                }
+            }
+
+            fn numeric(&self) -> Option<Numeric> {
+                match self {
+                    $($signed(x) => Some(Numeric::Int {
+                        negative: *x < 0,
+                        magnitude: (*x as i128).unsigned_abs(),
+                    }),)*
+                    $($unsigned(x) => Some(Numeric::Int {
+                        negative: false,
+                        magnitude: *x as u128,
+                    }),)*
+                    $($float(x) => Some(Numeric::Float(*x as f64)),)*
+                    _ => None,
+                }
+            }
+        }
+
+        $(impl From<$signed_ty> for Solution {
+            fn from(x: $signed_ty) -> Self {
+                $signed(x)
+            }
+        })*
+        $(impl From<$unsigned_ty> for Solution {
+            fn from(x: $unsigned_ty) -> Self {
+                $unsigned(x)
+            }
+        })*
+        $(impl From<$float_ty> for Solution {
+            fn from(x: $float_ty) -> Self {
+                $float(x)
+            }
+        })*
+        $(impl From<$other_ty> for Solution {
+            fn from(x: $other_ty) -> Self {
+                $other(x)
+            }
+        })*
+    };
+}
+
+solution! {
+    signed: I8(i8), I16(i16), I32(i32), I64(i64), I128(i128), Isize(isize);
+    unsigned: U8(u8), U16(u16), U32(u32), U64(u64), U128(u128), Usize(usize);
+    float: F32(f32), F64(f64);
+    other: Bool(bool), Char(char), Str(String), Grid(Grid), Big(BigInt);
+}
+
+impl From<BigUint> for Solution {
+    fn from(n: BigUint) -> Self {
+        Big(n.into())
+    }
+}
+
+impl Solution {
+    /// Promotes an integer to a `BigInt` without loss, `None` for other variants.
+    pub fn to_big(&self) -> Option<BigInt> {
+        match (self, self.numeric()) {
+            (Big(n), _) => Some(n.clone()),
+            (
+                _,
+                Some(Numeric::Int {
+                    negative,
+                    magnitude,
+                }),
+            ) => Some(BigInt::new(negative, magnitude.into())),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Solution {
+    fn from(s: &str) -> Self {
+        Str(s.to_string())
+    }
+}
+
+/// A multi-line answer, such as letters drawn as a picture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    lines: Vec<String>,
+}
+
+impl Grid {
+    /// Builds a grid from rows of characters.
+    #[allow(dead_code)] // None of the solved days draws its answer yet.
+    pub fn from_rows<R, C>(rows: R) -> Self
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = char>,
+    {
+        Grid {
+            lines: rows
+                .into_iter()
+                .map(|row| row.into_iter().collect())
+                .collect(),
+        }
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
 
-pub enum Solution {
-    I32(i32),
-    I64(i64),
-    I128(i128),
-    U32(u32),
-    U64(u64),
-    U128(u128),
-    Str(String),
+impl From<Vec<String>> for Grid {
+    fn from(lines: Vec<String>) -> Self {
+        Grid { lines }
+    }
 }
 
-impl Display for Solution {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+impl From<&str> for Grid {
+    fn from(s: &str) -> Self {
+        Grid {
+            lines: s.lines().map(String::from).collect(),
+        }
+    }
+}
+
+/// The lines separated by newlines, without a trailing one.
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.lines.join("\n"))
+    }
+}
+
+impl From<Vec<String>> for Solution {
+    fn from(lines: Vec<String>) -> Self {
+        Grid(lines.into())
+    }
+}
+
+impl Solution {
+    /// Whether the solution spans several lines.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Grid(grid) if grid.lines().len() > 1)
+    }
+}
+
+/// Value of a numeric solution, integers are compared exactly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Numeric {
+    Int { negative: bool, magnitude: u128 },
+    Float(f64),
+}
+
+impl Numeric {
+    fn as_f64(self) -> f64 {
         match self {
-            I32(x) => x.fmt(f),
-            I64(x) => x.fmt(f),
-            I128(x) => x.fmt(f),
-            U32(x) => x.fmt(f),
-            U64(x) => x.fmt(f),
-            U128(x) => x.fmt(f),
-            Str(x) => x.fmt(f),
+            Numeric::Int {
+                negative,
+                magnitude,
+            } => match negative {
+                true => -(magnitude as f64),
+                false => magnitude as f64,
+            },
+            Numeric::Float(x) => x,
+        }
+    }
+}
+
+/// Numbers are equal if their values are, regardless of the variant, so
+/// `U64(5) == I32(5)`. An `F32` is compared at `f32` precision, as it prints
+/// and is stored with that precision. Everything else is compared by its text.
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.to_big(), other.to_big()) {
+            return a == b;
+        }
+        let is_f32 = matches!(self, F32(_)) || matches!(other, F32(_));
+        match (self.numeric(), other.numeric()) {
+            (Some(a), Some(b)) if is_f32 => a.as_f64() as f32 == b.as_f64() as f32,
+            (Some(a), Some(b)) => a.as_f64() == b.as_f64(),
+            _ => self.to_string() == other.to_string(),
         }
     }
 }
 
+/// Reads a stored answer: integers become `I128`, `U128` or `Big`, other
+/// numbers `F64`, several lines `Grid` and anything else `Str`.
+///
+/// Only numbers written the way they are printed count as numbers, so `007`
+/// and `+5` stay strings and never match the solution 7 or 5.
+impl FromStr for Solution {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_number = s.chars().any(|c| c.is_ascii_digit())
+            && s.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+        Ok(
+            match (canonical(s), canonical(s), canonical(s), canonical(s)) {
+                (Some(x), _, _, _) => I128(x),
+                (_, Some(x), _, _) => U128(x),
+                (_, _, Some(x), _) => Big(x),
+                (_, _, _, Some(x)) if is_number => F64(x),
+                _ if s.contains('\n') => Grid(s.into()),
+                _ => Str(s.to_string()),
+            },
+        )
+    }
+}
+
+/// Parses `s` if it reads the same when printed again.
+fn canonical<T: FromStr + ToString>(s: &str) -> Option<T> {
+    s.parse().ok().filter(|x: &T| x.to_string() == s)
+}
+
+/// Answer of a single part.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Answer {
+    /// The part was not run, or the day has no such part.
+    #[default]
+    NotRun,
+    /// The solver does not solve this part yet.
+    Unsolved,
+    Solved(Solution),
+}
+
+impl Answer {
+    pub fn solution(&self) -> Option<&Solution> {
+        match self {
+            Answer::Solved(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::NotRun => f.write_str("—"),
+            Answer::Unsolved => f.write_str("not implemented"),
+            Answer::Solved(solution) => solution.fmt(f),
+        }
+    }
+}
+
+/// Answers of both parts of a day, indexed by `Part`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Answers {
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+impl Answers {
+    /// Whether a part that was run is not solved yet.
+    pub fn is_unsolved(&self) -> bool {
+        Part::ALL.iter().any(|&part| self[part] == Answer::Unsolved)
+    }
+
+    /// Whether no part was run, e.g. for `--part 2` on day 25.
+    pub fn is_not_run(&self) -> bool {
+        Part::ALL.iter().all(|&part| self[part] == Answer::NotRun)
+    }
+
+    /// Whether parts were run and none of them is solved, as for a stub.
+    pub fn is_unimplemented(&self) -> bool {
+        let run: Vec<&Answer> = Part::ALL
+            .iter()
+            .map(|&part| &self[part])
+            .filter(|answer| **answer != Answer::NotRun)
+            .collect();
+        !run.is_empty() && run.iter().all(|answer| **answer == Answer::Unsolved)
+    }
+}
+
+impl Index<Part> for Answers {
+    type Output = Answer;
+
+    fn index(&self, part: Part) -> &Answer {
+        match part {
+            Part::One => &self.part1,
+            Part::Two => &self.part2,
+        }
+    }
+}
+
+impl IndexMut<Part> for Answers {
+    fn index_mut(&mut self, part: Part) -> &mut Answer {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigUint, Grid, Solution};
+
+    #[test]
+    fn into() {
+        assert!(matches!(Solution::from(5usize), Solution::Usize(5)));
+        assert!(matches!(Solution::from(-3i8), Solution::I8(-3)));
+        assert!(matches!(Solution::from('x'), Solution::Char('x')));
+        assert_eq!(Solution::from("abc").variant(), "Str");
+        assert_eq!(Solution::from(1.5f64).to_string(), "1.5");
+    }
+
+    #[test]
+    fn numeric_eq() {
+        assert_eq!(Solution::U64(5), Solution::I32(5));
+        assert_eq!(Solution::U128(u128::MAX), Solution::U128(u128::MAX));
+        assert_ne!(Solution::I64(-5), Solution::U64(5));
+        assert_ne!(Solution::U128(u128::MAX), Solution::I128(-1));
+        assert_eq!(Solution::F64(2.0), Solution::U8(2));
+        assert_ne!(Solution::from("5"), Solution::F32(5.5));
+        assert_eq!(Solution::from(true), Solution::from("true"));
+        // Integers beyond the precision of f64 are still told apart.
+        let big = 1u128 << 60;
+        assert_ne!(Solution::U128(big), Solution::I64(big as i64 + 1));
+        assert_ne!(Solution::from(BigUint::from(big)), Solution::U128(big + 1));
+    }
+
+    #[test]
+    fn parse() {
+        let parse = |s: &str| s.parse::<Solution>().unwrap();
+        assert!(matches!(parse("-12"), Solution::I128(-12)));
+        assert_eq!(parse(&u128::MAX.to_string()), Solution::U128(u128::MAX));
+        assert!(matches!(parse("0.25"), Solution::F64(x) if x == 0.25));
+        assert!(matches!(parse("inf"), Solution::Str(_)));
+        assert_eq!(parse("007").variant(), "Str");
+        assert_eq!(parse("+5").variant(), "Str");
+        assert_ne!(parse("007"), Solution::U8(7));
+        assert_ne!(parse("+5"), Solution::I32(5));
+        assert_eq!(parse("0.1"), Solution::F32(0.1));
+        assert_ne!(parse("0.1000001"), Solution::F32(0.1));
+        for x in [0.1f32, 1.0 / 3.0, 1e-7, 123456.79] {
+            assert_eq!(parse(&Solution::F32(x).to_string()), Solution::F32(x));
+        }
+        assert_eq!(parse("ABC").to_string(), "ABC");
+        assert_eq!(parse("#.\n.#").variant(), "Grid");
+    }
+
+    #[test]
+    fn big() {
+        let n = BigUint::from(u128::MAX) * BigUint::from(10u8);
+        let solution = Solution::from(n.clone());
+        assert_eq!(solution.variant(), "Big");
+        assert_eq!(solution.to_string(), n.to_string());
+        assert_eq!(solution.to_string().parse::<Solution>().unwrap(), solution);
+
+        assert_eq!(Solution::from(BigUint::from(7u8)), Solution::U16(7));
+        assert_eq!(Solution::I64(-7).to_big().unwrap().to_string(), "-7");
+        assert_eq!(
+            Solution::from(u128::MAX).to_big().unwrap().to_u128(),
+            Some(u128::MAX)
+        );
+        assert!(Solution::from("7").to_big().is_none());
+    }
+
+    #[test]
+    fn grid() {
+        let grid = Grid::from_rows([[true, false], [false, true]].map(|row| {
+            row.map(|lit| match lit {
+                true => '#',
+                false => '.',
+            })
+        }));
+        let solution = Solution::from(grid);
+        assert!(solution.is_multiline());
+        assert_eq!(solution.to_string(), "#.\n.#");
+        assert_eq!(solution.to_string().parse::<Solution>().unwrap(), solution);
+    }
+}
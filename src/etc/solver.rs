@@ -0,0 +1,132 @@
+use super::{Answer, Answers, Cancel, Error, Solution};
+use anyhow::{bail, Result};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// A puzzle solution, split into parsing the input and solving both parts.
+///
+/// The parsed input is shared between the two parts, so each phase can be
+/// timed on its own. Long running parts should check `cancel` regularly, see
+/// `Cancel`.
+pub trait Solver {
+    type Parsed;
+
+    /// Day 25 only has a single part.
+    const HAS_PART2: bool = true;
+
+    /// Stubs set this to `false` until their parts are written, they are then
+    /// reported as not implemented without being run and `latest` skips them.
+    const IMPLEMENTED: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    fn part1(_parsed: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Err(Error::Unsolved.into())
+    }
+
+    fn part2(_parsed: &Self::Parsed, _cancel: &Cancel) -> Result<Solution> {
+        Err(Error::Unsolved.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Position of the part in arrays such as `Report::verdicts`.
+    pub fn index(self) -> usize {
+        match self {
+            Part::One => 0,
+            Part::Two => 1,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.index() + 1)
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => bail!("Invalid part '{s}', expected 1 or 2."),
+        }
+    }
+}
+
+/// Time spent in each phase of a solver, `None` for parts that were not run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+/// `run::<S>` with the solver erased, as stored in the day registry.
+pub type RunFn = fn(&str, Option<Part>, &Cancel) -> Result<(Answers, Timings)>;
+
+/// Runs `S` on `input`, timing each phase.
+///
+/// Only `part` is solved if given, otherwise both parts are.
+pub fn run<S: Solver>(
+    input: &str,
+    part: Option<Part>,
+    cancel: &Cancel,
+) -> Result<(Answers, Timings)> {
+    let time = Instant::now();
+    let parsed = S::parse(input)?;
+    let mut timings = Timings {
+        parse: time.elapsed(),
+        ..Timings::default()
+    };
+
+    let mut answers = Answers::default();
+    for p in Part::ALL {
+        if part.is_some_and(|part| part != p) || (p == Part::Two && !S::HAS_PART2) {
+            continue;
+        }
+        cancel.check()?;
+        let time = Instant::now();
+        let solution = match p {
+            Part::One => S::part1(&parsed, cancel),
+            Part::Two => S::part2(&parsed, cancel),
+        };
+        let elapsed = time.elapsed();
+        answers[p] = match solution {
+            Ok(solution) => Answer::Solved(solution),
+            Err(err) if err.downcast_ref::<Error>() == Some(&Error::Unsolved) => Answer::Unsolved,
+            Err(err) => return Err(err),
+        };
+        if answers[p] != Answer::Unsolved {
+            match p {
+                Part::One => timings.part1 = Some(elapsed),
+                Part::Two => timings.part2 = Some(elapsed),
+            }
+        }
+    }
+    Ok((answers, timings))
+}
+
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
@@ -0,0 +1,400 @@
+use super::{
+    client::{Client, Config},
+    input::{self, InputSource},
+    json::Json,
+    solver::Part,
+    Answer, Cancel, Error, Solution,
+};
+use crate::days;
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Directory holding the submitted answers of every day as `dayNN.jsonl`.
+pub const DIR: &str = ".aoc/submissions";
+
+/// Options of the `submit` subcommand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmitOptions {
+    pub day: u8,
+    pub part: Part,
+    pub input: InputSource,
+    /// Overrides `base_url` of the config, e.g. to use a mirror.
+    pub base_url: Option<String>,
+}
+
+/// How the website judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Nothing was judged, the next answer may be sent after the wait.
+    RateLimited(Duration),
+}
+
+impl Reply {
+    /// Reads the verdict from the page returned for a submission.
+    pub fn parse(page: &str) -> Result<Self> {
+        static RE_WAIT: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Ok(Reply::Correct)
+        } else if text.contains("That's not the right answer") {
+            Ok(match () {
+                _ if text.contains("too high") => Reply::TooHigh,
+                _ if text.contains("too low") => Reply::TooLow,
+                _ => Reply::Wrong,
+            })
+        } else if let Some(caps) = RE_WAIT.captures(&text) {
+            let minutes: u64 = caps.get(1).map_or(Ok(0), |m| m.as_str().parse())?;
+            let seconds: u64 = caps[2].parse()?;
+            Ok(Reply::RateLimited(Duration::from_secs(
+                60 * minutes + seconds,
+            )))
+        } else {
+            bail!("Unexpected reply: {text}")
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Reply::Correct => "correct",
+            Reply::Wrong => "wrong",
+            Reply::TooHigh => "too_high",
+            Reply::TooLow => "too_low",
+            Reply::RateLimited(_) => "rate_limited",
+        }
+    }
+
+    /// Whether the answer was judged and is not the solution.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Reply::Wrong | Reply::TooHigh | Reply::TooLow)
+    }
+}
+
+impl Display for Reply {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Reply::Correct => f.write_str("correct"),
+            Reply::Wrong => f.write_str("wrong"),
+            Reply::TooHigh => f.write_str("wrong, too high"),
+            Reply::TooLow => f.write_str("wrong, too low"),
+            Reply::RateLimited(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+        }
+    }
+}
+
+/// The text of the `<article>` holding the verdict, without markup.
+fn article_text(page: &str) -> String {
+    static RE_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let text = RE_TAG.replace_all(article, " ");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    /// Seconds since the epoch.
+    pub time: u64,
+    pub part: Part,
+    pub answer: String,
+    pub reply: Reply,
+}
+
+impl Guess {
+    fn to_json(&self) -> Json {
+        let wait = match self.reply {
+            Reply::RateLimited(wait) => Some(wait.as_secs()),
+            _ => None,
+        };
+        Json::object([
+            ("time", Json::from(self.time)),
+            ("part", Json::from(self.part.to_string())),
+            ("answer", Json::from(self.answer.as_str())),
+            ("reply", Json::from(self.reply.name())),
+            ("wait_secs", Json::from(wait)),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Result<Self> {
+        let reply = match json.str_field("reply")? {
+            "correct" => Reply::Correct,
+            "wrong" => Reply::Wrong,
+            "too_high" => Reply::TooHigh,
+            "too_low" => Reply::TooLow,
+            "rate_limited" => {
+                Reply::RateLimited(Duration::from_secs(json.num_field("wait_secs")? as u64))
+            }
+            reply => bail!("Unknown reply '{reply}'."),
+        };
+        Ok(Guess {
+            time: json.num_field("time")? as u64,
+            part: json.str_field("part")?.parse()?,
+            answer: json.str_field("answer")?.to_string(),
+            reply,
+        })
+    }
+
+    fn matches(&self, solution: &Solution) -> bool {
+        self.answer.parse() == Ok(solution.clone())
+    }
+}
+
+/// Every answer submitted for a day, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Log {
+    path: PathBuf,
+    pub guesses: Vec<Guess>,
+}
+
+impl Log {
+    pub fn path(day: u8) -> PathBuf {
+        Path::new(DIR).join(format!("day{day:02}.jsonl"))
+    }
+
+    /// Reads the log at `path`, empty if there is none yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let guesses = match path.is_file() {
+            true => fs::read_to_string(path)
+                .with_context(|| format!("Could not read '{}'.", path.display()))?
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    Json::parse(line)
+                        .and_then(|json| Guess::from_json(&json))
+                        .with_context(|| format!("{}:{}: invalid entry.", path.display(), i + 1))
+                })
+                .collect::<Result<_>>()?,
+            false => vec![],
+        };
+        Ok(Log {
+            path: path.to_path_buf(),
+            guesses,
+        })
+    }
+
+    /// Fails if sending `solution` at `now` would be pointless: the part is
+    /// already solved, the answer was rejected before or lies outside the
+    /// bounds given by too high and too low guesses, or the wait of a rate
+    /// limit is not over yet.
+    pub fn check(&self, part: Part, solution: &Solution, now: u64) -> Result<()> {
+        if let Some(Guess {
+            time,
+            reply: Reply::RateLimited(wait),
+            ..
+        }) = self.guesses.last()
+        {
+            let until = time + wait.as_secs();
+            if until > now {
+                bail!(
+                    "Rate limited, wait another {}s before submitting.",
+                    until - now
+                );
+            }
+        }
+
+        let big = solution.to_big();
+        for guess in self.guesses.iter().filter(|guess| guess.part == part) {
+            match guess.reply {
+                Reply::Correct if guess.matches(solution) => {
+                    bail!("{solution} was already accepted for part {part}.")
+                }
+                Reply::Correct => bail!("Part {part} was already solved with {}.", guess.answer),
+                reply if reply.is_wrong() && guess.matches(solution) => {
+                    bail!("{solution} was already rejected as {reply}.")
+                }
+                _ => {}
+            }
+            let bound = guess.answer.parse().ok().and_then(|s: Solution| s.to_big());
+            let (Some(big), Some(bound)) = (&big, bound) else {
+                continue;
+            };
+            match guess.reply {
+                Reply::TooHigh if *big >= bound => {
+                    bail!("{solution} is not below {bound}, which was too high.")
+                }
+                Reply::TooLow if *big <= bound => {
+                    bail!("{solution} is not above {bound}, which was too low.")
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn append(&mut self, guess: Guess) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create '{}'.", dir.display()))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", guess.to_json()))
+            .with_context(|| format!("Could not append to '{}'.", self.path.display()))?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+/// Sends `solution` as the answer of `part`, unless `log` rules it out,
+/// and records the reply in `log`.
+pub fn submit(
+    client: &Client,
+    log: &mut Log,
+    day: u8,
+    part: Part,
+    solution: &Solution,
+) -> Result<Reply> {
+    if solution.is_multiline() {
+        bail!("Multi-line answers cannot be submitted, read the letters and submit those.");
+    }
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    log.check(part, solution, time)?;
+
+    let answer = solution.to_string();
+    let page = client.post(
+        &format!("day/{day}/answer"),
+        &[("level", &part.to_string()), ("answer", &answer)],
+    )?;
+    let reply = Reply::parse(&page)?;
+    log.append(Guess {
+        time,
+        part,
+        answer,
+        reply,
+    })?;
+    Ok(reply)
+}
+
+/// Solves the selected part and submits the answer, printing the reply.
+pub fn run(options: &SubmitOptions) -> Result<Reply> {
+    let day = days::get(options.day).with_context(|| format!("Unknown day {}.", options.day))?;
+    if !day.has(options.part) {
+        bail!("Day {} has no part {}.", day.number, options.part);
+    }
+    let input = input::load(day.number, &options.input)?;
+    let (answers, _) = (day.run)(&input, Some(options.part), &Cancel::new())?;
+    let Answer::Solved(solution) = &answers[options.part] else {
+        bail!(Error::Unsolved);
+    };
+
+    let config = Config::load_with(options.base_url.as_deref())?;
+    let client = Client::new(&config)?;
+    let mut log = Log::load(&Log::path(day.number))?;
+
+    println!("Day {:02} part {}: {solution}", day.number, options.part);
+    let reply = submit(&client, &mut log, day.number, options.part, solution)?;
+    println!("{reply}");
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{submit, Log, Reply};
+    use crate::etc::{
+        client::{mock::Server, Client, Config},
+        solver::Part,
+        temp::TempDir,
+        Solution,
+    };
+    use std::time::Duration;
+
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer to restoring snow operations.</p></article></main>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p></article>";
+    const TOO_LOW: &str =
+        "<article><p>That's not the right answer; your answer is too low.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, there are some general tips on the <a href=\"/2023/about\">about page</a>.</p></article>";
+    const RATE_LIMITED: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article>";
+
+    #[test]
+    fn parse_reply() {
+        assert_eq!(Reply::parse(CORRECT).unwrap(), Reply::Correct);
+        assert_eq!(Reply::parse(TOO_HIGH).unwrap(), Reply::TooHigh);
+        assert_eq!(Reply::parse(TOO_LOW).unwrap(), Reply::TooLow);
+        assert_eq!(Reply::parse(WRONG).unwrap(), Reply::Wrong);
+        assert_eq!(
+            Reply::parse(RATE_LIMITED).unwrap(),
+            Reply::RateLimited(Duration::from_secs(65))
+        );
+        assert_eq!(
+            Reply::parse("<article><p>You have 30s left to wait.</p></article>").unwrap(),
+            Reply::RateLimited(Duration::from_secs(30))
+        );
+        assert!(Reply::parse("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").is_err());
+    }
+
+    #[test]
+    fn submit_to_mock() {
+        let server = Server::start(|request| {
+            let reply = match request.body.as_str() {
+                "level=1&answer=42" => CORRECT,
+                "level=1&answer=100" => TOO_HIGH,
+                "level=1&answer=10" => TOO_LOW,
+                "level=2&answer=7" => WRONG,
+                _ => RATE_LIMITED,
+            };
+            (200, reply.to_string())
+        });
+        let client = Client::new(&Config {
+            session: Some("abc".to_string()),
+            base_url: server.base_url.clone(),
+            ..Config::default()
+        })
+        .unwrap();
+        let dir = TempDir::new("submit");
+        let path = dir.join("day01.jsonl");
+        let mut log = Log::load(&path).unwrap();
+        let mut send =
+            |part, answer: i64| submit(&client, &mut log, 1, part, &Solution::from(answer));
+
+        assert_eq!(send(Part::One, 100).unwrap(), Reply::TooHigh);
+        assert_eq!(send(Part::One, 10).unwrap(), Reply::TooLow);
+        assert!(send(Part::One, 100).is_err());
+        assert!(send(Part::One, 150).is_err());
+        assert!(send(Part::One, 5).is_err());
+        assert_eq!(send(Part::One, 42).unwrap(), Reply::Correct);
+        assert!(send(Part::One, 43).is_err());
+        assert_eq!(send(Part::Two, 7).unwrap(), Reply::Wrong);
+        assert_eq!(
+            send(Part::Two, 8).unwrap(),
+            Reply::RateLimited(Duration::from_secs(65))
+        );
+        assert!(send(Part::Two, 9).is_err());
+        assert_eq!(server.requests().len(), 5);
+
+        let log = Log::load(&path).unwrap();
+        assert_eq!(log.guesses.len(), 5);
+        let limited = log.guesses[4].time;
+        assert!(log
+            .check(Part::Two, &Solution::from(9), limited + 60)
+            .is_err());
+        assert!(log
+            .check(Part::Two, &Solution::from(9), limited + 65)
+            .is_ok());
+        assert!(log
+            .check(Part::Two, &Solution::from(7), limited + 65)
+            .is_err());
+    }
+}
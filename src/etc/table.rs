@@ -0,0 +1,239 @@
+use super::{
+    answers::Verdict,
+    report::{Report, Status},
+    solver::Part,
+};
+use anyhow::bail;
+use std::{
+    env,
+    io::{self, IsTerminal},
+    str::FromStr,
+};
+
+/// Width of the runtime bar of the slowest day.
+const BAR_WIDTH: usize = 20;
+/// Longer answers are cut off to keep the table readable.
+const MAX_ANSWER_WIDTH: usize = 24;
+
+/// Order of the rows in the summary table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sort {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Time,
+}
+
+impl FromStr for Sort {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "time" => Ok(Sort::Time),
+            _ => bail!("Unknown sort order '{s}', expected day or time."),
+        }
+    }
+}
+
+/// ANSI colours, only used when stdout is a terminal and `NO_COLOR` is unset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colors {
+    enabled: bool,
+}
+
+impl Colors {
+    pub fn detect() -> Self {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Colors {
+            enabled: io::stdout().is_terminal() && !no_color,
+        }
+    }
+
+    fn paint(&self, text: &str, code: &str) -> String {
+        match self.enabled && !text.is_empty() {
+            true => format!("\x1b[{code}m{text}\x1b[0m"),
+            false => text.to_string(),
+        }
+    }
+}
+
+struct Row<'a> {
+    day: String,
+    parts: [String; 2],
+    status: &'static str,
+    runtime_ms: f64,
+    report: &'a Report,
+}
+
+/// Overall status of a day: the failure if it failed, otherwise the
+/// combined verdicts of its answers.
+fn status(report: &Report) -> &'static str {
+    if report.status != Status::Ok {
+        return report.status.name();
+    }
+    let verdicts: Vec<&Verdict> = report.verdicts.iter().flatten().collect();
+    if report.is_wrong() {
+        "wrong"
+    } else if report
+        .answers
+        .as_ref()
+        .is_some_and(|answers| answers.is_unsolved())
+    {
+        "unsolved"
+    } else if !verdicts.is_empty() && verdicts.iter().all(|v| **v == Verdict::Correct) {
+        "correct"
+    } else {
+        "unknown"
+    }
+}
+
+fn answer(report: &Report, part: Part) -> String {
+    let text = report.answer(part).to_string();
+    let first_line = text.lines().next().unwrap_or_default();
+    if first_line.chars().count() > MAX_ANSWER_WIDTH || first_line.len() < text.len() {
+        let cut: String = first_line.chars().take(MAX_ANSWER_WIDTH - 1).collect();
+        format!("{cut}…")
+    } else {
+        text
+    }
+}
+
+/// Renders one row per implemented day with its answers, status and runtime.
+pub fn render(reports: &[Report], sort: Sort, colors: Colors) -> String {
+    let mut rows: Vec<Row> = reports
+        .iter()
+        .filter(|report| report.status != Status::NotImplemented)
+        .map(|report| Row {
+            day: match &report.example {
+                Some(example) => format!("{:02} {example}", report.day),
+                None => format!("{:02}", report.day),
+            },
+            parts: Part::ALL.map(|part| answer(report, part)),
+            status: status(report),
+            runtime_ms: report.runtime_ms(),
+            report,
+        })
+        .collect();
+    if sort == Sort::Time {
+        rows.sort_by(|a, b| b.runtime_ms.total_cmp(&a.runtime_ms));
+    }
+
+    let width = |header: &str, cell: &dyn Fn(&Row) -> usize| {
+        rows.iter().map(cell).max().unwrap_or(0).max(header.len())
+    };
+    let day = width("Day", &|row| row.day.len());
+    let part1 = width("Part 1", &|row| row.parts[0].chars().count());
+    let part2 = width("Part 2", &|row| row.parts[1].chars().count());
+    let status_width = width("Status", &|row| row.status.len());
+    let times: Vec<String> = rows
+        .iter()
+        .map(|row| match row.report.timings {
+            Some(_) => format!("{:.4} ms", row.runtime_ms),
+            None => "—".to_string(),
+        })
+        .collect();
+    let time_width = times
+        .iter()
+        .map(|time| time.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let slowest = rows.iter().map(|row| row.runtime_ms).fold(0.0, f64::max);
+
+    let mut out = format!(
+        "{:<day$}  {:<part1$}  {:<part2$}  {:<status_width$}  {:>time_width$}\n",
+        "Day", "Part 1", "Part 2", "Status", "Time"
+    );
+    for (row, time) in rows.iter().zip(&times) {
+        let status = format!("{:<status_width$}", row.status);
+        let status = match row.status {
+            "correct" => colors.paint(&status, "32"),
+            "unknown" | "unsolved" => colors.paint(&status, "33"),
+            _ if row.report.status != Status::Ok || row.report.is_wrong() => {
+                colors.paint(&status, "31")
+            }
+            _ => status,
+        };
+        let bar = match slowest > 0.0 {
+            true => "█".repeat((row.runtime_ms / slowest * BAR_WIDTH as f64).round() as usize),
+            false => String::new(),
+        };
+        let pad = |text: &str, width: usize| {
+            format!("{text}{}", " ".repeat(width - text.chars().count()))
+        };
+        let line = format!(
+            "{}  {}  {}  {}  {:>time_width$} {}",
+            pad(&row.day, day),
+            pad(&row.parts[0], part1),
+            pad(&row.parts[1], part2),
+            status,
+            time,
+            colors.paint(&bar, "36"),
+        );
+        out += line.trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, Colors, Sort};
+    use crate::{
+        days,
+        etc::{
+            answers::Verdict,
+            report::{Report, Status},
+            solver::Timings,
+            Answer, Answers, Solution,
+        },
+    };
+    use std::time::Duration;
+
+    fn report(day: u8, millis: u64, verdict: Verdict) -> Report {
+        let mut report = Report::new(days::get(day).unwrap());
+        report.answers = Some(Answers {
+            part1: Answer::Solved(Solution::U64(day as u64 * 100)),
+            part2: Answer::NotRun,
+        });
+        report.verdicts = [Some(verdict), None];
+        report.timings = Some(Timings {
+            parse: Duration::from_millis(millis),
+            part1: None,
+            part2: None,
+        });
+        report
+    }
+
+    #[test]
+    fn rows() {
+        let mut failed = Report::new(days::get(3).unwrap());
+        failed.status = Status::Timeout(Duration::from_secs(1));
+        let reports = [
+            report(1, 10, Verdict::Correct),
+            report(
+                2,
+                40,
+                Verdict::Wrong {
+                    expected: "8".to_string(),
+                },
+            ),
+            failed,
+        ];
+
+        let table = render(&reports, Sort::Time, Colors { enabled: false });
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Day  Part 1  Part 2  Status "));
+        assert!(lines[1].starts_with("02   200     —       wrong  "));
+        assert!(lines[1].ends_with(&"█".repeat(20)));
+        assert!(lines[2].starts_with("01   100     —       correct"));
+        assert!(lines[2].ends_with(&format!(" {}", "█".repeat(5))));
+        assert!(lines[3].starts_with("03   —       —       timeout"));
+        assert!(lines[3].ends_with(" —"));
+
+        let table = render(&reports, Sort::Day, Colors { enabled: false });
+        assert!(table.lines().nth(1).unwrap().starts_with("01"));
+    }
+}
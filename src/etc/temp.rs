@@ -0,0 +1,35 @@
+use std::{
+    env, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// A scratch directory for tests, removed with its contents when dropped,
+/// also when an assertion fails halfway through the test.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates an empty directory, `name` keeps the directories of tests
+    /// running at the same time apart.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
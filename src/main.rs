@@ -1,80 +1,121 @@
 mod days;
 mod etc;
 
-use days::{
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+use etc::{
+    cli::USAGE,
+    extract, fetch, history,
+    report::{self, Format, Status},
+    runner,
+    solver::millis,
+    submit::{self, Reply},
+    Answers, Command, Error, Options,
 };
-use etc::solution::Solution;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::env;
+use std::process::ExitCode;
 use std::time::Instant;
 
-pub type SolutionPair = (Solution, Solution);
+/// Exit codes of a run, invalid arguments exit with 1. The highest one wins.
+const EXIT_WRONG: u8 = 2;
+const EXIT_ERROR: u8 = 3;
+const EXIT_PANIC: u8 = 4;
+const EXIT_TIMEOUT: u8 = 5;
+/// `compare` found a day that got slower than the threshold.
+const EXIT_SLOWER: u8 = 6;
+const EXIT_INPUT_MISSING: u8 = 7;
+const EXIT_PARSE: u8 = 8;
+const EXIT_UNSOLVED: u8 = 9;
+/// `submit` was told to wait before submitting again, retrying later may succeed.
+const EXIT_RATE_LIMITED: u8 = 10;
 
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Please provide the day(s) to run as a command-line argument.");
-    }
-
-    let days: Vec<u8> = args[1..]
-        .iter()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
-        })
-        .collect();
+/// Invalid arguments and errors outside of the days.
+const EXIT_FAILURE: u8 = 1;
 
-    let mut runtime = 0.0;
+fn main() -> ExitCode {
+    let code = Command::parse(env::args().skip(1)).and_then(execute);
+    ExitCode::from(code.unwrap_or_else(|err| {
+        eprintln!("Error: {err:#}");
+        EXIT_FAILURE
+    }))
+}
 
-    for day in days {
-        let func = get_day_solver(day);
+fn execute(command: Command) -> Result<u8> {
+    let code = match command {
+        Command::Run(options) => run(&options)?,
+        Command::Compare(options) => match history::print_compare(&options)? {
+            true => EXIT_SLOWER,
+            false => 0,
+        },
+        Command::Fetch(options) => match fetch::run(&options)? {
+            true => 0,
+            false => EXIT_ERROR,
+        },
+        Command::Submit(options) => match submit::run(&options)? {
+            Reply::Correct => 0,
+            Reply::RateLimited(_) => EXIT_RATE_LIMITED,
+            _ => EXIT_WRONG,
+        },
+        Command::Extract(options) => {
+            extract::run(&options)?;
+            0
+        }
+        Command::Help => {
+            println!("{USAGE}");
+            0
+        }
+    };
+    Ok(code)
+}
 
-        let time = Instant::now();
-        let (p1, p2) = func()?;
-        let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
+fn run(options: &Options) -> Result<u8> {
+    // Days without the selected part, i.e. day 25 with `--part 2`, are left out.
+    let selected: Vec<_> = options
+        .days
+        .iter()
+        .filter_map(|&day| days::get(day))
+        .filter(|day| options.part.is_none_or(|part| day.has(part)))
+        .collect();
+    if selected.is_empty() {
+        bail!("The selected days have no part 2.");
+    }
 
-        println!("\n=== Day {:02} ===", day);
-        println!("  · Part 1: {}", p1);
-        println!("  · Part 2: {}", p2);
-        println!("  · Elapsed: {:.4} ms", elapsed_ms);
+    let time = Instant::now();
+    let tasks = runner::tasks(&selected, options)?;
+    let reports = runner::run_days(&tasks, options, |report| {
+        if options.format == Format::Text {
+            report::print_text(report);
+        }
+    });
+    let wall_ms = millis(time.elapsed());
 
-        runtime += elapsed_ms;
+    match options.format {
+        Format::Text => report::print_text_summary(&reports, options, wall_ms),
+        Format::Json => report::print_json(&reports, wall_ms),
+        Format::Csv => report::print_csv(&reports),
     }
 
-    println!("Total runtime: {:.4} ms", runtime);
-    Ok(())
-}
-
-fn get_day_solver(day: u8) -> fn() -> Result<SolutionPair> {
-    match day {
-        1 => day01::solve,
-        2 => day02::solve,
-        3 => day03::solve,
-        4 => day04::solve,
-        5 => day05::solve,
-        6 => day06::solve,
-        7 => day07::solve,
-        8 => day08::solve,
-        9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        13 => day13::solve,
-        14 => day14::solve,
-        15 => day15::solve,
-        16 => day16::solve,
-        17 => day17::solve,
-        18 => day18::solve,
-        19 => day19::solve,
-        20 => day20::solve,
-        21 => day21::solve,
-        22 => day22::solve,
-        23 => day23::solve,
-        24 => day24::solve,
-        25 => day25::solve,
-        _ => unimplemented!(),
+    if options.bench.is_some() && !options.examples {
+        if let Err(err) = history::append(&reports, options) {
+            eprintln!("Warning: {err:#}");
+        }
     }
+
+    let code = reports
+        .iter()
+        .map(|report| match report.status {
+            Status::Timeout(_) => EXIT_TIMEOUT,
+            Status::Panic(_) => EXIT_PANIC,
+            Status::Error(Error::InputMissing { .. }) => EXIT_INPUT_MISSING,
+            Status::Error(Error::Parse { .. }) => EXIT_PARSE,
+            Status::Error(Error::Unsolved) => EXIT_UNSOLVED,
+            Status::Error(Error::Timeout) => EXIT_TIMEOUT,
+            Status::Error(Error::Internal(_)) => EXIT_ERROR,
+            _ if report.answers.as_ref().is_some_and(Answers::is_unsolved) => EXIT_UNSOLVED,
+            _ if report.is_wrong() && !options.record => EXIT_WRONG,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0);
+    Ok(code)
 }